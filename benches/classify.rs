@@ -0,0 +1,117 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    benches/classify.rs
+ *
+ * Purpose: Benchmarks of path classification, comparing the classifier
+ *          that uses byte-level searches with the one that walks the path
+ *          a character at a time.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+use libpath::libpath::util::{
+    unix,
+    windows,
+};
+
+use criterion::{
+    black_box,
+    criterion_group,
+    criterion_main,
+    BenchmarkId,
+    Criterion,
+    Throughput,
+};
+
+
+const UNIX_PATHS : &[(&str, &str)] = &[
+    ("short", "file.ext"),
+    ("medium", "/usr/local/share/doc/libpath/README.md"),
+    (
+        "long",
+        "/home/user/projects/libpath.Rust/target/debug/build/libpath-0123456789abcdef/out/generated/sub-dir/./../file.tar.gz",
+    ),
+];
+
+const WINDOWS_PATHS : &[(&str, &str)] = &[
+    ("short", "file.ext"),
+    ("medium", r"C:\Program Files\libpath\docs\README.md"),
+    (
+        "long",
+        r"\\server\share\Users\user\projects\libpath.Rust\target\debug\build\libpath-0123456789abcdef\out\generated\sub-dir\.\..\file.tar.gz",
+    ),
+];
+
+
+fn bench_unix(c : &mut Criterion) {
+    let mut group = c.benchmark_group("unix");
+
+    for &(name, path) in UNIX_PATHS {
+        group.throughput(Throughput::Bytes(path.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("path_classify", name), path, |b, path| {
+            b.iter(|| unix::path_classify(black_box(path), 0))
+        });
+        group.bench_with_input(BenchmarkId::new("path_classify_fast", name), path, |b, path| {
+            b.iter(|| unix::path_classify_fast(black_box(path), 0))
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_windows(c : &mut Criterion) {
+    let mut group = c.benchmark_group("windows");
+
+    for &(name, path) in WINDOWS_PATHS {
+        group.throughput(Throughput::Bytes(path.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("path_classify", name), path, |b, path| {
+            b.iter(|| windows::path_classify(black_box(path), 0))
+        });
+        group.bench_with_input(BenchmarkId::new("path_classify_fast", name), path, |b, path| {
+            b.iter(|| windows::path_classify_fast(black_box(path), 0))
+        });
+    }
+
+    group.finish();
+}
+
+
+criterion_group!(benches, bench_unix, bench_windows);
+criterion_main!(benches);
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
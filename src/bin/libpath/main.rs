@@ -0,0 +1,697 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/bin/libpath/main.rs
+ *
+ * Purpose: The libpath command-line tool.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+//! The `libpath` command-line tool, which makes the classification,
+//! normalisation, joining, conversion, and portability checking of this
+//! crate available to shell scripts and CI jobs.
+//!
+//! Each subcommand writes one line (or, for `expand` and `check`, one
+//! block) per path in human-readable form, or, with `--format json`, a
+//! JSON array with one object per path. Where no paths are given on the
+//! command-line they are read, one per line, from standard input.
+//!
+//! Each subcommand interprets paths according to `--syntax`, which, by
+//! default, detects the syntax of each path. The flags that moderate
+//! classification, such as `--ignore-slash-runs`, are accepted only by
+//! `classify` and `expand`.
+//!
+//! The exit status is 0 on success, 1 if any path could not be processed
+//! (or, for `check`, is not portable), and 2 for usage errors.
+
+use libpath::libpath::util::{
+    common::{
+        ClassificationResult,
+        ExpandedClassificationResult,
+    },
+    convert::{
+        self,
+        DriveMapping,
+    },
+    detect,
+    portability,
+    syntax::{
+        self,
+        Syntax,
+    },
+    unix,
+    windows,
+};
+
+use clap::{
+    Args,
+    Parser,
+    Subcommand,
+    ValueEnum,
+};
+use serde_json::{
+    json,
+    Value,
+};
+
+use std::{
+    io::{
+        self,
+        BufRead,
+        Write,
+    },
+    process::ExitCode,
+};
+
+
+#[derive(Debug)]
+#[derive(Parser)]
+#[command(
+    name = "libpath",
+    version,
+    about = "Classifies, normalises, joins, and converts UNIX and Windows paths"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command : Command,
+    #[command(flatten)]
+    options : Options,
+}
+
+#[derive(Debug)]
+#[derive(Args)]
+struct Options {
+    /// The syntax by which paths are interpreted; `auto` detects it from
+    /// each path, other than for `check`, which then checks each path in
+    /// both syntaxes
+    #[arg(long, value_enum, default_value_t = SyntaxChoice::Auto, global = true)]
+    syntax : SyntaxChoice,
+    /// The form of the output
+    #[arg(long, value_enum, default_value_t = Format::Human, global = true)]
+    format : Format,
+}
+
+#[derive(Debug)]
+#[derive(Args)]
+struct ClassificationOptions {
+    /// Runs of path-name separators are not considered invalid
+    #[arg(long)]
+    ignore_slash_runs :                 bool,
+    /// Invalid characters are not considered invalid
+    #[arg(long)]
+    ignore_invalid_chars :              bool,
+    /// A leading `~` is recognised as the home directory (at present it
+    /// always is, in both syntaxes)
+    #[arg(long)]
+    recognise_tilde_home :              bool,
+    /// `/c/dir` is recognised as a path on drive C: (UNIX only)
+    #[arg(long)]
+    recognise_msys_drives :             bool,
+    /// `/mnt/c/dir` is recognised as a path on drive C: (UNIX only)
+    #[arg(long)]
+    recognise_wsl_drives :              bool,
+    /// `/cygdrive/c/dir` is recognised as a path on drive C: (UNIX only)
+    #[arg(long)]
+    recognise_cygwin_drives :           bool,
+    /// All of the `--recognise-*-drives` flags (UNIX only)
+    #[arg(long)]
+    recognise_foreign_drives :          bool,
+    /// Invalid characters are not considered invalid in `\\?\` paths
+    /// (Windows only)
+    #[arg(long)]
+    ignore_invalid_chars_in_long_path : bool,
+}
+
+#[derive(Debug)]
+#[derive(Subcommand)]
+enum Command {
+    /// Classifies each path
+    Classify {
+        #[command(flatten)]
+        flags : ClassificationOptions,
+        paths : Vec<String>,
+    },
+    /// Normalises each path lexically
+    #[command(alias = "normalise")]
+    Normalize { paths : Vec<String> },
+    /// Obtains each path relative to a base directory
+    Relative { base : String, paths : Vec<String> },
+    /// Joins paths to a base directory, in order
+    Join {
+        base :  String,
+        #[arg(required = true)]
+        paths : Vec<String>,
+    },
+    /// Converts each path to another syntax, leaving those already in that
+    /// syntax unchanged
+    Convert {
+        /// The syntax to which paths are converted
+        #[arg(long, value_enum)]
+        to :      SyntaxName,
+        /// How Windows drives are expressed in UNIX paths
+        #[arg(long, value_enum, default_value_t = Mapping::Msys)]
+        mapping : Mapping,
+        paths :   Vec<String>,
+    },
+    /// Checks each path for portability between UNIX and Windows or, if
+    /// `--syntax` is `unix` or `windows`, within that syntax only
+    Check { paths : Vec<String> },
+    /// Classifies each path and shows each of its elements
+    Expand {
+        #[command(flatten)]
+        flags : ClassificationOptions,
+        paths : Vec<String>,
+    },
+}
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(ValueEnum)]
+enum SyntaxChoice {
+    Unix,
+    Windows,
+    Auto,
+}
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(ValueEnum)]
+enum SyntaxName {
+    Unix,
+    Windows,
+}
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(ValueEnum)]
+enum Format {
+    Human,
+    Json,
+}
+
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(ValueEnum)]
+enum Mapping {
+    Msys,
+    Wsl,
+    Cygwin,
+}
+
+
+// Non-mutating methods
+impl Options {
+    /// Resolves the syntax by which the given paths are interpreted,
+    /// detecting it, if `--syntax auto`, from the path with the most
+    /// evidence.
+    fn syntax_for(
+        &self,
+        paths : &[&str],
+    ) -> Syntax {
+        match self.syntax {
+            SyntaxChoice::Unix => Syntax::Unix,
+            SyntaxChoice::Windows => Syntax::Windows,
+            SyntaxChoice::Auto => {
+                paths
+                    .iter()
+                    .map(|path| detect::detect_syntax(path))
+                    .rev()
+                    .max_by_key(|&(_, confidence)| confidence)
+                    .map_or(Syntax::Unix, |(syntax, _)| syntax)
+            },
+        }
+    }
+}
+
+// Non-mutating methods
+impl ClassificationOptions {
+    /// Obtains the classification flags for `syntax`.
+    fn classification_flags(
+        &self,
+        syntax : Syntax,
+    ) -> i32 {
+        use syntax::classification_flags::*;
+
+        let mut flags = 0;
+
+        if self.ignore_slash_runs {
+            flags |= IGNORE_SLASH_RUNS;
+        }
+        if self.ignore_invalid_chars {
+            flags |= IGNORE_INVALID_CHARS;
+        }
+        if self.recognise_tilde_home {
+            flags |= RECOGNISE_TILDE_HOME;
+        }
+
+        match syntax {
+            Syntax::Unix => {
+                if self.recognise_msys_drives {
+                    flags |= unix::classification_flags::RECOGNISE_MSYS_DRIVES;
+                }
+                if self.recognise_wsl_drives {
+                    flags |= unix::classification_flags::RECOGNISE_WSL_DRIVES;
+                }
+                if self.recognise_cygwin_drives {
+                    flags |= unix::classification_flags::RECOGNISE_CYGWIN_DRIVES;
+                }
+                if self.recognise_foreign_drives {
+                    flags |= unix::classification_flags::RECOGNISE_FOREIGN_DRIVES;
+                }
+            },
+            Syntax::Windows => {
+                if self.ignore_invalid_chars_in_long_path {
+                    flags |= windows::classification_flags::IGNORE_INVALID_CHARS_IN_LONG_PATH;
+                }
+            },
+        }
+
+        flags
+    }
+}
+
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let options = &cli.options;
+
+    let outputs = match &cli.command {
+        Command::Classify {
+            flags,
+            paths,
+        } => each_(paths, |path| classify_(options, flags, path)),
+        Command::Normalize {
+            paths,
+        } => {
+            each_(paths, |path| {
+                let syntax = options.syntax_for(&[path]);
+
+                let normalised = match syntax {
+                    Syntax::Unix => unix::normalise(path),
+                    Syntax::Windows => windows::normalise(path),
+                };
+
+                Ok(Output::from_result(path, syntax, normalised))
+            })
+        },
+        Command::Relative {
+            base,
+            paths,
+        } => {
+            each_(paths, |path| {
+                let syntax = options.syntax_for(&[base, path]);
+
+                let relative = match syntax {
+                    Syntax::Unix => unix::relative_path(base, path),
+                    Syntax::Windows => windows::relative_path(base, path),
+                };
+
+                relative
+                    .map(|relative| Output::from_result(path, syntax, relative).with("base", base))
+                    .map_err(|e| Output::from_error(path, syntax, e).with("base", base))
+            })
+        },
+        Command::Join {
+            base,
+            paths,
+        } => {
+            let all : Vec<&str> = std::iter::once(base).chain(paths).map(String::as_str).collect();
+
+            let syntax = options.syntax_for(&all);
+
+            let joined = paths.iter().fold(base.clone(), |joined, path| {
+                match syntax {
+                    Syntax::Unix => unix::join(&joined, path),
+                    Syntax::Windows => windows::join(&joined, path),
+                }
+            });
+
+            Ok(vec![Output {
+                human :     format!("{joined}\n"),
+                json :      json!({
+                    "base": base,
+                    "paths": paths,
+                    "syntax": syntax_name_(syntax),
+                    "result": joined,
+                }),
+                succeeded : true,
+            }])
+        },
+        Command::Convert {
+            to,
+            mapping,
+            paths,
+        } => {
+            let mapping = match mapping {
+                Mapping::Msys => DriveMapping::Msys,
+                Mapping::Wsl => DriveMapping::Wsl,
+                Mapping::Cygwin => DriveMapping::Cygwin,
+            };
+
+            each_(paths, |path| {
+                let syntax = options.syntax_for(&[path]);
+
+                let converted = match (syntax, to) {
+                    (Syntax::Unix, SyntaxName::Windows) => convert::unix_to_windows(path, mapping),
+                    (Syntax::Windows, SyntaxName::Unix) => convert::windows_to_unix(path, mapping),
+                    _ => Ok(path.to_string()),
+                };
+
+                converted
+                    .map(|converted| Output::from_result(path, syntax, converted))
+                    .map_err(|e| Output::from_error(path, syntax, e))
+            })
+        },
+        Command::Check {
+            paths,
+        } => each_(paths, |path| check_(options, path)),
+        Command::Expand {
+            flags,
+            paths,
+        } => each_(paths, |path| expand_(options, flags, path)),
+    };
+
+    let outputs = match outputs {
+        Ok(outputs) => outputs,
+        Err(e) => {
+            eprintln!("libpath: could not read paths from standard input: {e}");
+
+            return ExitCode::FAILURE;
+        },
+    };
+
+    if let Err(e) = write_(options.format, &outputs) {
+        // a closed pipe, as in `libpath classify ... | head`, is not an error
+        if io::ErrorKind::BrokenPipe != e.kind() {
+            eprintln!("libpath: could not write output: {e}");
+
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if outputs.iter().all(|output| output.succeeded) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+
+/// The output for one path, in each of the formats.
+struct Output {
+    human :     String,
+    json :      Value,
+    succeeded : bool,
+}
+
+// API functions
+impl Output {
+    /// Creates an instance whose result is a single path.
+    fn from_result(
+        path : &str,
+        syntax : Syntax,
+        result : String,
+    ) -> Self {
+        Self {
+            human :     format!("{result}\n"),
+            json :      json!({
+                "path": path,
+                "syntax": syntax_name_(syntax),
+                "result": result,
+            }),
+            succeeded : true,
+        }
+    }
+
+    /// Creates an instance that describes why `path` could not be
+    /// processed.
+    fn from_error(
+        path : &str,
+        syntax : Syntax,
+        error : impl std::fmt::Display,
+    ) -> Self {
+        Self {
+            human :     String::new(),
+            json :      json!({
+                "path": path,
+                "syntax": syntax_name_(syntax),
+                "error": error.to_string(),
+            }),
+            succeeded : false,
+        }
+    }
+}
+
+// Mutating methods
+impl Output {
+    /// Adds a member to the JSON form.
+    fn with(
+        mut self,
+        name : &str,
+        value : impl serde::Serialize,
+    ) -> Self {
+        self.json[name] = json!(value);
+
+        self
+    }
+}
+
+
+/// Writes the outputs, in the given format, to standard output, and the
+/// error, if any, of each failed output to standard error.
+fn write_(
+    format : Format,
+    outputs : &[Output],
+) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+
+    match format {
+        Format::Human => {
+            for output in outputs {
+                if let Some(error) = output.json.get("error") {
+                    let path = output.json["path"].as_str().unwrap_or_default();
+
+                    eprintln!("libpath: {path}: {}", error.as_str().unwrap_or_default());
+                } else {
+                    stdout.write_all(output.human.as_bytes())?;
+                }
+            }
+        },
+        Format::Json => {
+            let values : Vec<&Value> = outputs.iter().map(|output| &output.json).collect();
+
+            serde_json::to_writer_pretty(&mut stdout, &values)?;
+
+            writeln!(stdout)?;
+        },
+    }
+
+    stdout.flush()
+}
+
+/// Applies `f` to each of `paths`, or, if there are none, to each line of
+/// standard input.
+fn each_<F>(
+    paths : &[String],
+    mut f : F,
+) -> io::Result<Vec<Output>>
+where
+    F : FnMut(&str) -> Result<Output, Output>,
+{
+    let mut apply = |path : &str| f(path).unwrap_or_else(|output| output);
+
+    if paths.is_empty() {
+        io::stdin()
+            .lock()
+            .lines()
+            .map(|line| line.map(|line| apply(&line)))
+            .collect()
+    } else {
+        Ok(paths.iter().map(|path| apply(path)).collect())
+    }
+}
+
+fn syntax_name_(syntax : Syntax) -> &'static str {
+    match syntax {
+        Syntax::Unix => "unix",
+        Syntax::Windows => "windows",
+    }
+}
+
+fn classify_with_(
+    options : &Options,
+    flags : &ClassificationOptions,
+    path : &str,
+) -> (
+    Syntax,               // syntax
+    String,               // classification
+    ClassificationResult, // classification_result
+) {
+    let syntax = options.syntax_for(&[path]);
+    let flags = flags.classification_flags(syntax);
+
+    let (classification, cr) = match syntax {
+        Syntax::Unix => {
+            let (cl, cr) = unix::path_classify(path, flags);
+
+            (format!("{cl:?}"), cr)
+        },
+        Syntax::Windows => {
+            let (cl, cr) = windows::path_classify(path, flags);
+
+            (format!("{cl:?}"), cr)
+        },
+    };
+
+    (syntax, classification, cr)
+}
+
+fn classify_(
+    options : &Options,
+    flags : &ClassificationOptions,
+    path : &str,
+) -> Result<Output, Output> {
+    let (syntax, classification, cr) = classify_with_(options, flags, path);
+
+    Ok(Output {
+        human :     format!("{path}: {classification} ({})\n", syntax_name_(syntax)),
+        json :      json!({
+            "path": path,
+            "syntax": syntax_name_(syntax),
+            "classification": classification,
+            "classification_result": cr,
+        }),
+        succeeded : true,
+    })
+}
+
+fn expand_(
+    options : &Options,
+    flags : &ClassificationOptions,
+    path : &str,
+) -> Result<Output, Output> {
+    let (syntax, classification, cr) = classify_with_(options, flags, path);
+
+    let ecr = ExpandedClassificationResult::new(path, &cr);
+
+    let mut human = format!("{path}: {classification} ({})\n", syntax_name_(syntax));
+
+    for (name, value) in [
+        ("Prefix", &ecr.Prefix),
+        ("Location", &ecr.Location),
+        ("Root", &ecr.Root),
+        ("Directory", &ecr.Directory),
+        ("EntryName", &ecr.EntryName),
+        ("Stem", &ecr.Stem),
+        ("Extension", &ecr.Extension),
+        ("FirstInvalid", &ecr.FirstInvalid),
+    ] {
+        human.push_str(&format!("  {name:<22} \"{value}\"\n"));
+    }
+    human.push_str(&format!("  {:<22} {}\n", "NumDirectoryParts", ecr.NumDirectoryParts));
+    human.push_str(&format!(
+        "  {:<22} {}\n",
+        "NumDotsDirectoryParts", ecr.NumDotsDirectoryParts
+    ));
+
+    Ok(Output {
+        human,
+        json : json!({
+            "path": path,
+            "syntax": syntax_name_(syntax),
+            "classification": classification,
+            "expanded": ecr,
+        }),
+        succeeded : true,
+    })
+}
+
+fn check_(
+    options : &Options,
+    path : &str,
+) -> Result<Output, Output> {
+    // unless a syntax is specified, each path is checked in both
+
+    let report = match options.syntax {
+        SyntaxChoice::Unix => portability::check_for_syntax(path, Syntax::Unix),
+        SyntaxChoice::Windows => portability::check_for_syntax(path, Syntax::Windows),
+        SyntaxChoice::Auto => portability::check(path),
+    };
+
+    let mut human = format!(
+        "{path}: {}\n",
+        if report.is_portable() {
+            "portable"
+        } else {
+            "not portable"
+        }
+    );
+    let mut problems = Vec::new();
+
+    for problem in &report.problems {
+        let text = problem.position.substring_of(path);
+
+        human.push_str(&format!(
+            "  {}: {} (\"{text}\")\n",
+            problem.position.offset, problem.kind
+        ));
+
+        problems.push(json!({
+            "kind": format!("{:?}", problem.kind),
+            "description": problem.kind.to_string(),
+            "offset": problem.position.offset,
+            "length": problem.position.len(),
+            "text": text,
+        }));
+    }
+
+    Ok(Output {
+        human,
+        json : json!({
+            "path": path,
+            "portable": report.is_portable(),
+            "problems": problems,
+        }),
+        succeeded : report.is_portable(),
+    })
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -4,7 +4,7 @@
  * Purpose: Primary implementation file for libpath.Rust.
  *
  * Created: 16th April 2021
- * Updated: 8th April 2025
+ * Updated: 18th October 2026
  *
  * Home:    http://stlsoft.org/
  *
@@ -39,6 +39,12 @@
  * ////////////////////////////////////////////////////////////////////// */
 
 
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+
 pub mod libpath {
 
     pub mod util {
@@ -47,7 +53,30 @@ pub mod libpath {
             #![allow(non_camel_case_types)]
             #![allow(non_snake_case)]
 
-            use fastparse::fastparse::types::PositionalSlice as PoSl;
+            mod components;
+            #[cfg(feature = "alloc")]
+            mod expanded;
+            #[cfg(feature = "alloc")]
+            pub(crate) mod file_uri;
+            mod positional_slice;
+
+            #[cfg(feature = "std")]
+            pub use self::components::StdComponents;
+            #[cfg(feature = "alloc")]
+            pub use self::expanded::ExpandedClassificationResult;
+            #[cfg(feature = "alloc")]
+            pub use self::file_uri::FileUriError;
+            pub use self::{
+                components::{
+                    Component,
+                    Components,
+                },
+                positional_slice::PositionalSlice,
+            };
+
+            use self::positional_slice::PositionalSlice as PoSl;
+
+            use core::fmt;
 
 
             /// Describes the classification.
@@ -55,8 +84,11 @@ pub mod libpath {
             /// A given full path will have the following elements:
             /// - FullPath - the full
             /// - Prefix
+            #[derive(Clone, Copy)]
             #[derive(Debug)]
             #[derive(PartialEq, Eq)]
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+            #[cfg_attr(feature = "serde", serde(try_from = "ClassificationResultRepr"))]
             pub struct ClassificationResult {
                 /// The input string's position.
                 pub Input :                 PoSl,
@@ -114,37 +146,265 @@ pub mod libpath {
                 }
             }
 
+            impl ClassificationResult {
+                /// Indicates whether every element lies within `path` and
+                /// on `char` boundaries, i.e. whether it is safe to use the
+                /// instance to slice `path`.
+                ///
+                /// # Note:
+                /// An instance that is deserialised is checked only for
+                /// internal consistency - that each element lies within
+                /// `Input` - since the path is not known, and so should be
+                /// checked with this before it is used to slice a path that
+                /// is obtained separately.
+                pub fn is_consistent_with(
+                    &self,
+                    path : &str,
+                ) -> bool {
+                    self.elements_().iter().all(|ps| {
+                        match ps.offset.checked_add(ps.length) {
+                            Some(end) => path.get(ps.offset..end).is_some(),
+                            None => false,
+                        }
+                    })
+                }
+
+                /// Indicates whether every element lies within `Input`.
+                #[cfg_attr(not(feature = "serde"), allow(dead_code))]
+                fn is_internally_consistent_(&self) -> bool {
+                    let input_end = match self.Input.offset.checked_add(self.Input.length) {
+                        Some(end) => end,
+                        None => return false,
+                    };
+
+                    self.elements_().iter().all(|ps| {
+                        match ps.offset.checked_add(ps.length) {
+                            Some(end) => self.Input.offset <= ps.offset && end <= input_end,
+                            None => false,
+                        }
+                    })
+                }
+
+                fn elements_(&self) -> [PoSl; 10] {
+                    [
+                        self.Input,
+                        self.FullPath,
+                        self.Prefix,
+                        self.Location,
+                        self.Root,
+                        self.Directory,
+                        self.EntryName,
+                        self.Stem,
+                        self.Extension,
+                        self.FirstInvalid,
+                    ]
+                }
+            }
+
+            /// Deserialisation form of [`ClassificationResult`], which is
+            /// checked for internal consistency before conversion.
+            #[cfg(feature = "serde")]
+            #[derive(serde::Deserialize)]
+            struct ClassificationResultRepr {
+                Input :                 PoSl,
+                FullPath :              PoSl,
+                Prefix :                PoSl,
+                Location :              PoSl,
+                Root :                  PoSl,
+                Directory :             PoSl,
+                NumDirectoryParts :     usize,
+                NumDotsDirectoryParts : usize,
+                EntryName :             PoSl,
+                Stem :                  PoSl,
+                Extension :             PoSl,
+                FirstInvalid :          PoSl,
+            }
+
+            #[cfg(feature = "serde")]
+            impl TryFrom<ClassificationResultRepr> for ClassificationResult {
+                type Error = &'static str;
+
+                fn try_from(repr : ClassificationResultRepr) -> Result<Self, Self::Error> {
+                    let cr = Self {
+                        Input :                 repr.Input,
+                        FullPath :              repr.FullPath,
+                        Prefix :                repr.Prefix,
+                        Location :              repr.Location,
+                        Root :                  repr.Root,
+                        Directory :             repr.Directory,
+                        NumDirectoryParts :     repr.NumDirectoryParts,
+                        NumDotsDirectoryParts : repr.NumDotsDirectoryParts,
+                        EntryName :             repr.EntryName,
+                        Stem :                  repr.Stem,
+                        Extension :             repr.Extension,
+                        FirstInvalid :          repr.FirstInvalid,
+                    };
+
+                    if !cr.is_internally_consistent_() {
+                        return Err("classification result has an element outside of its input");
+                    }
+
+                    Ok(cr)
+                }
+            }
+
+
+            /// Error returned when a path cannot be represented as a UTF-8
+            /// string.
+            #[derive(Clone, Copy)]
+            #[derive(Debug)]
+            #[derive(PartialEq, Eq)]
+            pub struct PathNotUnicodeError;
+
+            impl fmt::Display for PathNotUnicodeError {
+                fn fmt(
+                    &self,
+                    f : &mut fmt::Formatter<'_>,
+                ) -> fmt::Result {
+                    f.write_str("path is not valid Unicode")
+                }
+            }
+
+            #[cfg(feature = "std")]
+            impl std::error::Error for PathNotUnicodeError {
+            }
+
 
             #[cfg(test)]
             mod tests {
                 #![allow(non_snake_case)]
 
-                /*
                 use super::*;
-                 */
+
+                #[test]
+                fn TEST_ClassificationResult_is_consistent_with() {
+                    let path = "/dir/caf\u{e9}.ext";
+                    let (_, cr) = super::super::unix::path_classify(path, 0);
+
+                    assert!(cr.is_consistent_with(path));
+                    assert!(!cr.is_consistent_with("/dir/"));
+                    assert!(ClassificationResult::empty().is_consistent_with(""));
+
+                    let mut cr2 = cr;
+
+                    cr2.Stem = PoSl::new(cr.Stem.offset, 4);
+
+                    assert!(!cr2.is_consistent_with(path), "ends within 'é'");
+
+                    cr2.Stem = PoSl::new(usize::MAX, 1);
+
+                    assert!(!cr2.is_consistent_with(path));
+                }
+
+                #[cfg(feature = "serde")]
+                #[test]
+                fn TEST_ClassificationResult_serde_ROUNDTRIP() {
+                    for path in ["", "/", "~", "/dir/file.ext", "dir/.hidden", "a\0b"] {
+                        let (_, cr) = super::super::unix::path_classify(path, 0);
+
+                        let json = serde_json::to_string(&cr).unwrap();
+                        let cr2 : ClassificationResult = serde_json::from_str(&json).unwrap();
+
+                        assert_eq!(cr, cr2, "path='{path}'");
+                    }
+
+                    for path in [r"C:\dir\file.ext", r"\\server\share\", r"\\?\C:\x", "C:", r"a\b?"] {
+                        let (_, cr) = super::super::windows::path_classify(path, 0);
+
+                        let json = serde_json::to_string(&cr).unwrap();
+                        let cr2 : ClassificationResult = serde_json::from_str(&json).unwrap();
+
+                        assert_eq!(cr, cr2, "path='{path}'");
+                    }
+                }
+
+                #[cfg(feature = "serde")]
+                #[test]
+                fn TEST_ClassificationResult_serde_REJECTS_INCONSISTENT_RESULT() {
+                    let (_, cr) = super::super::unix::path_classify("/dir/file.ext", 0);
+
+                    for (element, ps) in [
+                        ("Stem", PoSl::new(9, 5)),
+                        ("Extension", PoSl::new(usize::MAX, 1)),
+                        ("Input", PoSl::new(1, usize::MAX)),
+                        ("FirstInvalid", PoSl::new(14, 0)),
+                    ] {
+                        let mut value = serde_json::to_value(cr).unwrap();
+
+                        value[element] = serde_json::to_value(ps).unwrap();
+
+                        assert!(
+                            serde_json::from_value::<ClassificationResult>(value).is_err(),
+                            "element={element}"
+                        );
+                    }
+                }
             }
         }
 
 
         pub mod unix {
 
-            use super::common::ClassificationResult;
-            use fastparse::fastparse::types::PositionalSlice as PoSl;
+            #[cfg(feature = "alloc")]
+            mod file_uri;
+
+            #[cfg(feature = "alloc")]
+            pub use self::file_uri::{
+                from_file_uri,
+                to_file_uri,
+            };
+
+            use super::{
+                common::{
+                    ClassificationResult,
+                    PositionalSlice as PoSl,
+                },
+                syntax::{
+                    self,
+                    CodeUnit,
+                    PathSyntax,
+                },
+            };
+
+            #[cfg(feature = "alloc")]
+            use alloc::string::String;
+            #[cfg(feature = "std")]
+            use std::ffi::OsStr;
 
             pub mod classification_flags {
 
                 /// T.B.C.
                 pub const IGNORE_SLASH_RUNS : i32 = 0x00000001;
-                /// T.B.C.
+                /// NUL characters do not cause the path to be classified as
+                /// [`InvalidChars`](super::Classification::InvalidChars).
                 pub const IGNORE_INVALID_CHARS : i32 = 0x00000002;
                 /// T.B.C.
                 pub const RECOGNISE_TILDE_HOME : i32 = 0x00000004;
+                /// Paths of the form `/c/...`, in which the drive `C:` is
+                /// mounted as by MSYS and Git Bash, are classified as
+                /// [`ForeignDriveRooted`](super::Classification::ForeignDriveRooted).
+                pub const RECOGNISE_MSYS_DRIVES : i32 = 0x00000010;
+                /// Paths of the form `/mnt/c/...`, in which the drive `C:` is
+                /// mounted as by WSL, are classified as
+                /// [`ForeignDriveRooted`](super::Classification::ForeignDriveRooted).
+                pub const RECOGNISE_WSL_DRIVES : i32 = 0x00000020;
+                /// Paths of the form `/cygdrive/c/...`, in which the drive
+                /// `C:` is mounted as by Cygwin, are classified as
+                /// [`ForeignDriveRooted`](super::Classification::ForeignDriveRooted).
+                pub const RECOGNISE_CYGWIN_DRIVES : i32 = 0x00000040;
+                /// All of [`RECOGNISE_MSYS_DRIVES`], [`RECOGNISE_WSL_DRIVES`],
+                /// and [`RECOGNISE_CYGWIN_DRIVES`].
+                pub const RECOGNISE_FOREIGN_DRIVES : i32 =
+                    RECOGNISE_MSYS_DRIVES | RECOGNISE_WSL_DRIVES | RECOGNISE_CYGWIN_DRIVES;
             }
 
 
             /// Path classification result
+            #[derive(Clone, Copy)]
             #[derive(Debug)]
-            #[derive(PartialEq)]
+            #[derive(PartialEq, Eq, PartialOrd, Ord)]
+            #[derive(Hash)]
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
             pub enum Classification {
                 InvalidSlashRuns = -3,
                 InvalidChars = -2,
@@ -154,239 +414,361 @@ pub mod libpath {
                 Relative,
                 SlashRooted,
                 _Reserved1,
-                _Reserved2,
+                /// A path on a Windows drive that is mounted in the UNIX
+                /// file-system, such as `/mnt/c/Users`, in which case the
+                /// root is the mount directory of the drive, e.g. `/mnt/c`.
+                ///
+                /// Only obtained when one or more of the
+                /// `RECOGNISE_*_DRIVES` flags is specified.
+                ForeignDriveRooted,
                 _Reserved3,
                 _Reserved4,
                 HomeRooted,
             }
 
 
-            pub fn path_classify(
-                path : &str,
-                parse_flags : i32,
-            ) -> (
-                Classification,       // classification
-                ClassificationResult, // classification_result
-            ) {
-                if path.is_empty() {
-                    return (
-                        // argument list:
-                        Classification::Empty,
-                        ClassificationResult::empty(),
-                    );
+            /// The UNIX path syntax.
+            #[derive(Clone, Copy)]
+            #[derive(Debug)]
+            #[derive(Default)]
+            #[derive(PartialEq, Eq, PartialOrd, Ord)]
+            #[derive(Hash)]
+            pub struct UnixSyntax;
+
+            impl PathSyntax for UnixSyntax {
+                type Classification = Classification;
+
+                const EMPTY : Classification = Classification::Empty;
+                const INVALID_CHARS : Classification = Classification::InvalidChars;
+                const IS_CASE_SENSITIVE : bool = true;
+                const PREFERRED_SEPARATOR : char = '/';
+                const RELATIVE : Classification = Classification::Relative;
+
+                fn char_is_path_name_separator(c : char) -> bool {
+                    c == '/'
                 }
 
-                let mut cr = ClassificationResult::empty();
-
-                cr.Input = PoSl::new(0, path.len());
-
-                let (cl, root, path_root_stripped) = classify_root_(path, parse_flags);
-
-                cr.Root = PoSl::new(0, root.len());
-
-                // now search within root-stripped path
-
-                let last_slash = find_last_slash_(path_root_stripped.substring_of(path));
-
-                match last_slash {
-                    Some(index) => {
-                        // if there's a slash, then there is a directory and, potentially, an entry
-
-                        let dir_len = index + 1;
-
-                        cr.Directory = PoSl::new(root.len(), dir_len);
-
-                        let (num_parts, num_dir_parts) = count_directory_parts_(cr.Directory.substring_of(path), parse_flags);
+                fn char_is_invalid(c : char) -> bool {
+                    c == '\0'
+                }
 
-                        cr.NumDirectoryParts = num_parts;
-                        cr.NumDotsDirectoryParts = num_dir_parts;
+                fn classify_root<U : CodeUnit>(
+                    path : &[U],
+                    parse_flags : i32,
+                ) -> (
+                    Classification, // classification
+                    PoSl,           // root
+                    PoSl,           // path_root_stripped
+                ) {
+                    debug_assert!(!path.is_empty());
+
+                    let c = |ix : usize| -> char { path.get(ix).map_or('\0', |&u| u.as_char()) };
+
+                    let (c0, c1) = (c(0), c(1));
+
+                    if '~' == c0 && (1 == path.len() || Self::char_is_path_name_separator(c1)) {
+                        return (
+                            // argument list:
+                            Classification::HomeRooted,
+                            PoSl::new(0, 1),
+                            PoSl::new(1, path.len() - 1),
+                        );
+                    }
 
-                        cr.EntryName = PoSl::new(root.len() + dir_len, path_root_stripped.len() - dir_len);
-                    },
-                    None => {
-                        cr.Directory = PoSl::new(root.len(), 0);
+                    if Self::char_is_path_name_separator(c0) {
+                        if let Some(root_len) = find_foreign_drive_root_(path, parse_flags) {
+                            return (
+                                // argument list:
+                                Classification::ForeignDriveRooted,
+                                PoSl::new(0, root_len),
+                                PoSl::new(root_len, path.len() - root_len),
+                            );
+                        }
 
-                        // if there's no slash, then the whole (stripped) path is the entry
+                        return (
+                            // argument list:
+                            Classification::SlashRooted,
+                            PoSl::empty(),
+                            PoSl::new(0, path.len()),
+                        );
+                    }
 
-                        cr.EntryName = PoSl::new(root.len(), path_root_stripped.len());
-                    },
+                    (
+                        // argument list:
+                        Classification::Relative,
+                        PoSl::empty(),
+                        PoSl::new(0, path.len()),
+                    )
                 }
 
-                if cr.EntryName.is_empty() {
-                    cr.Stem = cr.EntryName;
-                    cr.Extension = cr.EntryName;
-                } else {
-                    let last_entry_dot = cr.EntryName.substring_of(path).rfind('.');
+                fn is_home_rooted(classification : Classification) -> bool {
+                    Classification::HomeRooted == classification
+                }
+            }
 
-                    match last_entry_dot {
-                        Some(index) => {
-                            // handle special dots directories "." and ".."
 
-                            let mut is_dots = false;
+            /// A UNIX path together with its classification.
+            #[cfg(feature = "alloc")]
+            pub type ClassifiedPath<'a> = syntax::ClassifiedPath<'a, UnixSyntax>;
 
-                            if !is_dots && 1 == cr.EntryName.len() {
-                                is_dots = true;
-                            }
+            /// The key of a UNIX path, for use in a `HashMap` or `BTreeMap`.
+            #[cfg(feature = "alloc")]
+            pub type PathKey = syntax::PathKey<UnixSyntax>;
 
-                            if !is_dots {
-                                if 2 == cr.EntryName.len() {
-                                    if ".." == cr.EntryName.substring_of(path) {
-                                        is_dots = true;
-                                    }
-                                }
-                            }
+            /// Compact storage for large numbers of UNIX paths.
+            #[cfg(feature = "alloc")]
+            pub type PathArena = syntax::PathArena<UnixSyntax>;
 
-                            if is_dots {
-                                cr.Stem = cr.EntryName;
-                                cr.Extension = PoSl::new(cr.EntryName.len(), 0);
-                            } else {
-                                cr.Stem = PoSl::new(cr.EntryName.offset, index);
-                                cr.Extension = PoSl::new(cr.EntryName.offset + index, cr.EntryName.len() - index);
-                            }
-                        },
-                        None => {
-                            cr.Stem = cr.EntryName;
-                            cr.Extension = PoSl::new(cr.EntryName.offset + cr.EntryName.len(), 0);
-                        },
-                    }
-                }
+            /// A map from UNIX paths to values that supports prefix queries.
+            #[cfg(feature = "alloc")]
+            pub type PathTrie<V> = syntax::PathTrie<V, UnixSyntax>;
 
-                cr.Location = PoSl::new(0, cr.EntryName.offset);
 
-                (cl, cr)
+            /// Classifies a UNIX path.
+            ///
+            /// # Parameters:
+            /// - `path` - the given path to be classified;
+            /// - `parse_flags` - flags that moderate the classification;
+            pub fn path_classify(
+                path : &str,
+                parse_flags : i32,
+            ) -> (
+                Classification,       // classification
+                ClassificationResult, // classification_result
+            ) {
+                syntax::path_classify::<UnixSyntax>(path, parse_flags)
             }
 
-            /// Examines the path to the degree necessary to be able to
-            /// classify it.
+            /// Classifies a UNIX path using byte-level searches, obtaining results
+            /// identical to those of [`path_classify()`].
+            ///
+            /// See [`syntax::path_classify_fast()`] for details.
             ///
             /// # Parameters:
             /// - `path` - the given path to be classified;
             /// - `parse_flags` - flags that moderate the classification;
-            ///
-            /// # Returns:
-            /// `(classification : Classification, root : PositionalSlice, path_root_stripped : PositionalSlice)`
-            fn classify_root_(
+            pub fn path_classify_fast(
                 path : &str,
                 parse_flags : i32,
             ) -> (
-                Classification, // classification
-                PoSl,           // root
-                PoSl,           // path_root_stripped
+                Classification,       // classification
+                ClassificationResult, // classification_result
             ) {
-                debug_assert!(!path.is_empty());
+                syntax::path_classify_fast::<UnixSyntax>(path, parse_flags)
+            }
 
-                {
-                    let _ = parse_flags;
+            /// Classifies a UNIX path expressed as an arbitrary sequence of
+            /// bytes, such as is obtained from `readdir()`.
+            ///
+            /// Bytes that do not form valid UTF-8 are treated as ordinary
+            /// name bytes. For valid UTF-8 input the result is identical to
+            /// that of [`path_classify()`].
+            ///
+            /// # Parameters:
+            /// - `path` - the given path to be classified;
+            /// - `parse_flags` - flags that moderate the classification;
+            pub fn path_classify_bytes(
+                path : &[u8],
+                parse_flags : i32,
+            ) -> (
+                Classification,       // classification
+                ClassificationResult, // classification_result
+            ) {
+                syntax::path_classify_units::<UnixSyntax, u8>(path, parse_flags)
+            }
+
+            /// Obtains the drive letter of a path that has been classified
+            /// as [`ForeignDriveRooted`](Classification::ForeignDriveRooted),
+            /// e.g. `'c'` for `"/mnt/c/Users"`.
+            ///
+            /// # Parameters:
+            /// - `path` - the classified path;
+            /// - `classification` - the classification of `path`;
+            /// - `cr` - the classification result of `path`;
+            ///
+            /// # Returns:
+            /// `None` if `classification` is not
+            /// [`ForeignDriveRooted`](Classification::ForeignDriveRooted).
+            pub fn foreign_drive_letter(
+                path : &str,
+                classification : Classification,
+                cr : &ClassificationResult,
+            ) -> Option<char> {
+                if Classification::ForeignDriveRooted != classification {
+                    return None;
                 }
 
-                let mut tilde_0 = false;
+                cr.Root.substring_of(path).chars().next_back()
+            }
 
-                let mut ix = -1;
-                for c in path.chars() {
-                    ix += 1;
+            /// Obtains the longest common ancestor directory of a number
+            /// of UNIX paths.
+            ///
+            /// See [`syntax::common_ancestor()`] for details.
+            pub fn common_ancestor<'a>(paths : impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+                syntax::common_ancestor::<UnixSyntax, _>(paths)
+            }
 
-                    if '~' == c && 0 == ix {
-                        tilde_0 = true;
+            /// Normalises a UNIX path lexically.
+            ///
+            /// See [`syntax::normalise()`] for details.
+            #[cfg(feature = "alloc")]
+            pub fn normalise(path : &str) -> String {
+                syntax::normalise::<UnixSyntax>(path)
+            }
 
-                        continue;
-                    }
+            /// Determines, lexically, whether the UNIX path `candidate` is
+            /// contained within the directory `base`, obtaining their
+            /// normalised join if it is.
+            ///
+            /// See [`syntax::is_contained_within()`] for details.
+            #[cfg(feature = "alloc")]
+            pub fn is_contained_within(
+                base : &str,
+                candidate : &str,
+            ) -> Result<String, syntax::ContainmentError> {
+                syntax::is_contained_within::<UnixSyntax>(base, candidate)
+            }
 
-                    if char_is_path_name_separator_(c) {
-                        if 0 == ix {
-                            return (
-                                // argument list:
-                                Classification::SlashRooted,
-                                PoSl::empty(),
-                                PoSl::new(0, path.len()),
-                            );
-                        }
+            /// Joins the UNIX path `path` to the directory `base`.
+            ///
+            /// See [`syntax::join()`] for details.
+            #[cfg(feature = "alloc")]
+            pub fn join(
+                base : &str,
+                path : &str,
+            ) -> String {
+                syntax::join::<UnixSyntax>(base, path)
+            }
 
-                        if 1 == ix {
-                            return (
-                                // argument list:
-                                Classification::HomeRooted,
-                                PoSl::new(0, 1),
-                                PoSl::new(1, path.len() - 1),
-                            );
-                        }
-                    }
+            /// Obtains the UNIX path that identifies `path` relative to the
+            /// directory `base`.
+            ///
+            /// See [`syntax::relative_path()`] for details.
+            #[cfg(feature = "alloc")]
+            pub fn relative_path(
+                base : &str,
+                path : &str,
+            ) -> Result<String, syntax::RelativePathError> {
+                syntax::relative_path::<UnixSyntax>(base, path)
+            }
 
-                    break;
-                }
+            /// Evaluates whether two UNIX paths are equal.
+            ///
+            /// See [`syntax::paths_equal()`] for details.
+            #[cfg(feature = "alloc")]
+            pub fn paths_equal(
+                lhs : &str,
+                rhs : &str,
+                comparison_flags : i32,
+            ) -> bool {
+                syntax::paths_equal::<UnixSyntax>(lhs, rhs, comparison_flags)
+            }
 
-                if 0 == ix && tilde_0 {
-                    return (
-                        // argument list:
-                        Classification::HomeRooted,
-                        PoSl::new(0, 1),
-                        PoSl::new(1, path.len() - 1),
-                    );
-                }
+            /// Compares two UNIX paths.
+            ///
+            /// See [`syntax::path_cmp()`] for details.
+            #[cfg(feature = "alloc")]
+            pub fn path_cmp(
+                lhs : &str,
+                rhs : &str,
+                comparison_flags : i32,
+            ) -> core::cmp::Ordering {
+                syntax::path_cmp::<UnixSyntax>(lhs, rhs, comparison_flags)
+            }
 
-                (
-                    // argument list:
-                    Classification::Relative,
-                    PoSl::empty(),
-                    PoSl::new(0, path.len()),
-                )
+            /// Obtains the key of a UNIX path, for use in a `HashMap` or
+            /// `BTreeMap`.
+            ///
+            /// See [`syntax::path_key()`] for details.
+            #[cfg(feature = "alloc")]
+            pub fn path_key(
+                path : &str,
+                comparison_flags : i32,
+            ) -> PathKey {
+                syntax::path_key::<UnixSyntax>(path, comparison_flags)
             }
 
-            /// Evaluates whether a character is a path-name-separator.
-            fn char_is_path_name_separator_(c : char) -> bool {
-                c == '/'
+            /// Applies a Unicode normal form to each name in a UNIX
+            /// path.
+            ///
+            /// See [`syntax::normalise_unicode()`] for details.
+            #[cfg(feature = "unicode")]
+            pub fn normalise_unicode(
+                path : &str,
+                form : syntax::NormalForm,
+            ) -> String {
+                syntax::normalise_unicode::<UnixSyntax>(path, form)
             }
 
-            /// Looks for the last slash in the slice.
-            fn find_last_slash_(s : &str) -> Option<usize> {
-                s.rfind('/')
+            /// Evaluates whether each name in a UNIX path is in a
+            /// Unicode normal form.
+            ///
+            /// See [`syntax::is_unicode_normalised()`] for details.
+            #[cfg(feature = "unicode")]
+            pub fn is_unicode_normalised(
+                path : &str,
+                form : syntax::NormalForm,
+            ) -> bool {
+                syntax::is_unicode_normalised::<UnixSyntax>(path, form)
             }
 
-            fn count_directory_parts_(
-                s : &str,
+            /// Classifies a UNIX path expressed as an `OsStr`.
+            ///
+            /// The positions in the result are offsets into
+            /// `path.as_encoded_bytes()` which, on UNIX, are the bytes of
+            /// the path as given to / obtained from the operating system.
+            ///
+            /// # Parameters:
+            /// - `path` - the given path to be classified;
+            /// - `parse_flags` - flags that moderate the classification;
+            #[cfg(feature = "std")]
+            pub fn path_classify_os_str(
+                path : &OsStr,
                 parse_flags : i32,
             ) -> (
-                usize, // number_of_parts
-                usize, // number_of_dots_parts
+                Classification,       // classification
+                ClassificationResult, // classification_result
             ) {
-                {
-                    let _ = parse_flags;
-                }
+                path_classify_bytes(path.as_encoded_bytes(), parse_flags)
+            }
 
-                // This function counts the number of directory parts and
-                // the number of those that are dots directories
+            /// Obtains the length of the root of a path that begins with
+            /// the mount directory of a Windows drive, e.g. 6 for
+            /// `"/mnt/c/Users"`, according to the `RECOGNISE_*_DRIVES`
+            /// flags in `parse_flags`.
+            fn find_foreign_drive_root_<U : CodeUnit>(
+                path : &[U],
+                parse_flags : i32,
+            ) -> Option<usize> {
+                use classification_flags::*;
 
-                let mut number_of_parts = 0usize;
-                let mut number_of_dots_parts = 0usize;
+                let mounts = [
+                    // insert list:
+                    (RECOGNISE_WSL_DRIVES, "/mnt/"),
+                    (RECOGNISE_CYGWIN_DRIVES, "/cygdrive/"),
+                    (RECOGNISE_MSYS_DRIVES, "/"),
+                ];
 
-                let mut prev = 'X';
+                let c = |ix : usize| -> char { path.get(ix).map_or('\0', |&u| u.as_char()) };
 
-                let mut num_dots = 0;
+                for (flag, mount) in mounts {
+                    if 0 == (parse_flags & flag) {
+                        continue;
+                    }
 
-                for c in s.chars() {
-                    if char_is_path_name_separator_(c) {
-                        match num_dots {
-                            1 | 2 => number_of_dots_parts += 1,
-                            _ => (),
-                        }
+                    if !mount.chars().enumerate().all(|(ix, ch)| ch == c(ix)) {
+                        continue;
+                    }
 
-                        if char_is_path_name_separator_(prev) {
-                        } else {
-                            number_of_parts += 1;
-                        }
+                    let drive_ix = mount.len();
 
-                        num_dots = 0;
-                    } else {
-                        if '.' == c {
-                            num_dots += 1;
-                        } else {
-                            num_dots += 100;
-                        }
+                    if c(drive_ix).is_ascii_alphabetic() && (drive_ix + 1 == path.len() || '/' == c(drive_ix + 1)) {
+                        return Some(drive_ix + 1);
                     }
-
-                    prev = c;
                 }
 
-                (number_of_parts, number_of_dots_parts)
+                None
             }
 
 
@@ -395,14 +777,14 @@ pub mod libpath {
                 #![allow(non_snake_case)]
 
                 use super::{
-                    char_is_path_name_separator_,
                     classification_flags,
-                    classify_root_,
-                    count_directory_parts_,
+                    find_foreign_drive_root_,
                     Classification,
+                    PathSyntax,
+                    UnixSyntax,
                 };
 
-                use fastparse::fastparse::types::PositionalSlice as PoSl;
+                use crate::libpath::util::common::PositionalSlice as PoSl;
 
 
                 #[test]
@@ -411,25 +793,66 @@ pub mod libpath {
 
                 #[test]
                 fn TEST_char_is_path_name_separator__1() {
-                    assert!(char_is_path_name_separator_('/'));
-                    assert!(!char_is_path_name_separator_('\\'));
+                    assert!(UnixSyntax::char_is_path_name_separator('/'));
+                    assert!(!UnixSyntax::char_is_path_name_separator('\\'));
 
-                    assert!(!char_is_path_name_separator_('-'));
-                    assert!(!char_is_path_name_separator_(';'));
-                    assert!(!char_is_path_name_separator_(':'));
-                    assert!(!char_is_path_name_separator_('a'));
+                    assert!(!UnixSyntax::char_is_path_name_separator('-'));
+                    assert!(!UnixSyntax::char_is_path_name_separator(';'));
+                    assert!(!UnixSyntax::char_is_path_name_separator(':'));
+                    assert!(!UnixSyntax::char_is_path_name_separator('a'));
                 }
 
                 #[test]
                 fn TEST_classify_root__1() {
-                }
+                    let parse_flags = classification_flags::RECOGNISE_TILDE_HOME;
 
-                #[test]
-                fn TEST_count_directory_parts__1() {
+                    assert_eq!(
+                        (Classification::Relative, PoSl::empty(), PoSl::new(0, 3)),
+                        UnixSyntax::classify_root(b"abc", parse_flags)
+                    );
+                    assert_eq!(
+                        (Classification::SlashRooted, PoSl::empty(), PoSl::new(0, 4)),
+                        UnixSyntax::classify_root(b"/abc", parse_flags)
+                    );
+                    assert_eq!(
+                        (Classification::HomeRooted, PoSl::new(0, 1), PoSl::new(1, 4)),
+                        UnixSyntax::classify_root(b"~/abc", parse_flags)
+                    );
+                    assert_eq!(
+                        (Classification::HomeRooted, PoSl::new(0, 1), PoSl::new(1, 0)),
+                        UnixSyntax::classify_root(b"~", parse_flags)
+                    );
+                    assert_eq!(
+                        (Classification::Relative, PoSl::empty(), PoSl::new(0, 4)),
+                        UnixSyntax::classify_root(b"~abc", parse_flags)
+                    );
+                    assert_eq!(
+                        (Classification::SlashRooted, PoSl::empty(), PoSl::new(0, 9)),
+                        UnixSyntax::classify_root(b"/mnt/c/ab", parse_flags)
+                    );
+                    assert_eq!(
+                        (Classification::ForeignDriveRooted, PoSl::new(0, 6), PoSl::new(6, 3)),
+                        UnixSyntax::classify_root(b"/mnt/c/ab", classification_flags::RECOGNISE_WSL_DRIVES)
+                    );
                 }
 
                 #[test]
-                fn TEST_find_last_slash__1() {
+                fn TEST_find_foreign_drive_root__1() {
+                    use classification_flags::*;
+
+                    assert_eq!(None, find_foreign_drive_root_(b"/mnt/c/dir", 0));
+                    assert_eq!(Some(6), find_foreign_drive_root_(b"/mnt/c/dir", RECOGNISE_WSL_DRIVES));
+                    assert_eq!(Some(6), find_foreign_drive_root_(b"/mnt/c", RECOGNISE_WSL_DRIVES));
+                    assert_eq!(None, find_foreign_drive_root_(b"/mnt/cd/dir", RECOGNISE_WSL_DRIVES));
+                    assert_eq!(None, find_foreign_drive_root_(b"/mnt/c/dir", RECOGNISE_MSYS_DRIVES));
+                    assert_eq!(Some(2), find_foreign_drive_root_(b"/c/dir", RECOGNISE_MSYS_DRIVES));
+                    assert_eq!(None, find_foreign_drive_root_(b"/1/dir", RECOGNISE_MSYS_DRIVES));
+                    assert_eq!(
+                        Some(11),
+                        find_foreign_drive_root_(b"/cygdrive/d/dir", RECOGNISE_CYGWIN_DRIVES)
+                    );
+                    assert_eq!(Some(2), find_foreign_drive_root_(b"/c/mnt/d", RECOGNISE_FOREIGN_DRIVES));
+                    assert_eq!(Some(6), find_foreign_drive_root_(b"/mnt/d", RECOGNISE_FOREIGN_DRIVES));
                 }
             }
         }
@@ -437,25 +860,62 @@ pub mod libpath {
 
         pub mod windows {
 
-            use super::common::ClassificationResult;
-            use fastparse::fastparse::types::PositionalSlice as PoSl;
+            #[cfg(feature = "alloc")]
+            mod drive_directories;
+            #[cfg(feature = "alloc")]
+            mod file_uri;
+
+            #[cfg(feature = "alloc")]
+            pub use self::drive_directories::{
+                DriveDirectories,
+                DriveResolutionError,
+            };
+            #[cfg(feature = "alloc")]
+            pub use self::file_uri::{
+                from_file_uri,
+                to_file_uri,
+            };
+
+            use super::{
+                common::{
+                    ClassificationResult,
+                    PositionalSlice as PoSl,
+                },
+                syntax::{
+                    self,
+                    CodeUnit,
+                    PathSyntax,
+                },
+            };
+
+            #[cfg(feature = "alloc")]
+            use alloc::string::String;
 
             pub mod classification_flags {
 
                 /// T.B.C.
                 pub const IGNORE_SLASH_RUNS : i32 = 0x00000001;
-                /// T.B.C.
+                /// Characters that may not appear in a Windows file name -
+                /// `'<'`, `'>'`, `':'`, `'"'`, `'|'`, `'?'`, `'*'`, and the
+                /// control characters - do not cause the path to be
+                /// classified as
+                /// [`InvalidChars`](super::Classification::InvalidChars).
                 pub const IGNORE_INVALID_CHARS : i32 = 0x00000002;
                 /// T.B.C.
                 pub const RECOGNISE_TILDE_HOME : i32 = 0x00000004;
-                /// T.B.C.
-                pub const IGNORE_INVALID_CHARS_IN_LONG_PATH : i32 = 0x00000002;
+                /// As [`IGNORE_INVALID_CHARS`], but only for paths that have
+                /// the `\\?\` prefix, whose names Windows does not
+                /// validate.
+                pub const IGNORE_INVALID_CHARS_IN_LONG_PATH : i32 = 0x00000008;
             }
 
 
             /// Path classification result
+            #[derive(Clone, Copy)]
             #[derive(Debug)]
-            #[derive(PartialEq)]
+            #[derive(PartialEq, Eq, PartialOrd, Ord)]
+            #[derive(Hash)]
+            #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
             pub enum Classification {
                 InvalidSlashRuns = -3,
                 InvalidChars = -2,
@@ -464,302 +924,625 @@ pub mod libpath {
                 Empty,
                 Relative,
                 SlashRooted,
+                /// A path that begins with a drive designator that is not
+                /// followed by a separator, and so is relative to the
+                /// current directory of that drive, e.g. `C:dir\file` or
+                /// just `C:`.
                 DriveLetterRelative,
                 DriveLetterRooted,
+                /// A path that begins with two separators, or a `\\?\UNC\`
+                /// or `\\.\UNC\` prefix, that is not followed by both a
+                /// server name and a share name, e.g. `\\server`.
                 UncIncomplete,
+                /// A path that begins with two separators, or a `\\?\UNC\`
+                /// or `\\.\UNC\` prefix, followed by a server name and a
+                /// share name, which together form the root, e.g.
+                /// `\\server\share\dir\file`.
                 UncRooted,
                 HomeRooted,
+                /// A path in the Win32 device namespace, i.e. with a `\\.\`
+                /// or `\\?\` prefix, whose device name is part of the root,
+                /// e.g. `\\.\COM1` or `\\?\Volume{...}\dir`, other than
+                /// a UNC path or a path on a drive.
+                DeviceRooted,
+                /// A UNC path on the `wsl$` or `wsl.localhost` server, in
+                /// which case the share name is the name of the WSL
+                /// distribution, e.g. `\\wsl.localhost\Ubuntu\home`.
+                WslRooted,
             }
 
 
-            pub fn path_classify(
-                path : &str,
-                parse_flags : i32,
-            ) -> (
-                Classification,       // classification
-                ClassificationResult, // classification_result
-            ) {
-                if path.is_empty() {
-                    return (
-                        // argument list:
-                        Classification::Empty,
-                        ClassificationResult::empty(),
-                    );
+            /// The Windows path syntax.
+            #[derive(Clone, Copy)]
+            #[derive(Debug)]
+            #[derive(Default)]
+            #[derive(PartialEq, Eq, PartialOrd, Ord)]
+            #[derive(Hash)]
+            pub struct WindowsSyntax;
+
+            impl PathSyntax for WindowsSyntax {
+                type Classification = Classification;
+
+                const EMPTY : Classification = Classification::Empty;
+                const INVALID_CHARS : Classification = Classification::InvalidChars;
+                const IS_CASE_SENSITIVE : bool = false;
+                const PREFERRED_SEPARATOR : char = '\\';
+                const RELATIVE : Classification = Classification::Relative;
+
+                fn char_is_path_name_separator(c : char) -> bool {
+                    matches!(c, '/' | '\\')
+                }
+
+                fn char_is_invalid(c : char) -> bool {
+                    matches!(c, '<' | '>' | ':' | '"' | '|' | '?' | '*') || c.is_ascii_control() && '\x7F' != c
                 }
 
-                let mut cr = ClassificationResult::empty();
+                fn classify_root<U : CodeUnit>(
+                    path : &[U],
+                    parse_flags : i32,
+                ) -> (
+                    Classification, // classification
+                    PoSl,           // root
+                    PoSl,           // path_root_stripped
+                ) {
+                    debug_assert!(!path.is_empty());
+
+                    {
+                        let _ = parse_flags;
+                    }
+
+                    let c = |ix : usize| -> char { path.get(ix).map_or('\0', |&u| u.as_char()) };
 
-                cr.Input = PoSl::new(0, path.len());
+                    let (c0, c1, c2) = (c(0), c(1), c(2));
 
-                let (cl, root, path_root_stripped) = classify_root_(path, parse_flags);
+                    // "\\?\" and "\\.\" prefixed paths
+
+                    let prefix_len = Self::prefix_length(path);
+
+                    if 0 != prefix_len {
+                        if 8 == prefix_len {
+                            return classify_unc_root_(path, prefix_len);
+                        }
 
-                cr.Root = PoSl::new(0, root.len());
+                        if '?' == c2 && char_is_drive_letter_(c(4)) && ':' == c(5) {
+                            let classification = if Self::char_is_path_name_separator(c(6)) {
+                                Classification::DriveLetterRooted
+                            } else {
+                                Classification::DriveLetterRelative
+                            };
 
-                // now search within root-stripped path
+                            return (
+                                // argument list:
+                                classification,
+                                PoSl::new(0, 6),
+                                PoSl::new(6, path.len() - 6),
+                            );
+                        }
 
-                let last_slash = find_last_slash_(path_root_stripped.substring_of(path));
+                        // the device name is part of the root
 
-                match last_slash {
-                    Some(index) => {
-                        // if there's a slash, then there is a directory and, potentially, an entry
+                        let device_end = find_next_slash_(path, prefix_len);
 
-                        let dir_len = index + 1;
+                        return (
+                            // argument list:
+                            Classification::DeviceRooted,
+                            PoSl::new(0, device_end),
+                            PoSl::new(device_end, path.len() - device_end),
+                        );
+                    }
 
-                        cr.Directory = PoSl::new(root.len(), dir_len);
+                    // UNC paths
 
-                        let (num_parts, num_dir_parts) = count_directory_parts_(cr.Directory.substring_of(path), parse_flags);
+                    if Self::char_is_path_name_separator(c0) && Self::char_is_path_name_separator(c1) {
+                        return classify_unc_root_(path, 2);
+                    }
 
-                        cr.NumDirectoryParts = num_parts;
-                        cr.NumDotsDirectoryParts = num_dir_parts;
+                    if char_is_drive_letter_(c0) && ':' == c1 {
+                        let classification = if Self::char_is_path_name_separator(c2) {
+                            Classification::DriveLetterRooted
+                        } else {
+                            Classification::DriveLetterRelative
+                        };
+
+                        return (
+                            // argument list:
+                            classification,
+                            PoSl::new(0, 2),
+                            PoSl::new(2, path.len() - 2),
+                        );
+                    }
 
-                        cr.EntryName = PoSl::new(root.len() + dir_len, path_root_stripped.len() - dir_len);
-                    },
-                    None => {
-                        cr.Directory = PoSl::new(root.len(), 0);
+                    if '~' == c0 && (1 == path.len() || Self::char_is_path_name_separator(c1)) {
+                        return (
+                            // argument list:
+                            Classification::HomeRooted,
+                            PoSl::new(0, 1),
+                            PoSl::new(1, path.len() - 1),
+                        );
+                    }
 
-                        // if there's no slash, then the whole (stripped) path is the entry
+                    if Self::char_is_path_name_separator(c0) {
+                        return (
+                            // argument list:
+                            Classification::SlashRooted,
+                            PoSl::empty(),
+                            PoSl::new(0, path.len()),
+                        );
+                    }
 
-                        cr.EntryName = PoSl::new(root.len(), path_root_stripped.len());
-                    },
+                    (
+                        // argument list:
+                        Classification::Relative,
+                        PoSl::empty(),
+                        PoSl::new(0, path.len()),
+                    )
                 }
 
-                if cr.EntryName.is_empty() {
-                    cr.Stem = cr.EntryName;
-                    cr.Extension = cr.EntryName;
-                } else {
-                    let last_entry_dot = cr.EntryName.substring_of(path).rfind('.');
+                fn prefix_length<U : CodeUnit>(path : &[U]) -> usize {
+                    let c = |ix : usize| -> char { path.get(ix).map_or('\0', |&u| u.as_char()) };
+
+                    if '\\' != c(0) || '\\' != c(1) || !matches!(c(2), '?' | '.') || '\\' != c(3) {
+                        return 0;
+                    }
 
-                    match last_entry_dot {
-                        Some(index) => {
-                            // handle special dots directories "." and ".."
+                    // "\\?\UNC\" and "\\.\UNC\" are both UNC prefixes
 
-                            let mut is_dots = false;
+                    if "UNC"
+                        .chars()
+                        .enumerate()
+                        .all(|(ix, ch)| ch.eq_ignore_ascii_case(&c(4 + ix)))
+                        && '\\' == c(7)
+                    {
+                        return 8;
+                    }
 
-                            if !is_dots && 1 == cr.EntryName.len() {
-                                is_dots = true;
-                            }
+                    4
+                }
 
-                            if !is_dots {
-                                if 2 == cr.EntryName.len() {
-                                    if ".." == cr.EntryName.substring_of(path) {
-                                        is_dots = true;
-                                    }
-                                }
-                            }
+                fn ignores_invalid_chars<U : CodeUnit>(
+                    path : &[U],
+                    parse_flags : i32,
+                ) -> bool {
+                    use classification_flags::*;
 
-                            if is_dots {
-                                cr.Stem = cr.EntryName;
-                                cr.Extension = PoSl::new(cr.EntryName.len(), 0);
-                            } else {
-                                cr.Stem = PoSl::new(cr.EntryName.offset, index);
-                                cr.Extension = PoSl::new(cr.EntryName.offset + index, cr.EntryName.len() - index);
-                            }
-                        },
-                        None => {
-                            cr.Stem = cr.EntryName;
-                            cr.Extension = PoSl::new(cr.EntryName.offset + cr.EntryName.len(), 0);
-                        },
+                    if 0 != (parse_flags & IGNORE_INVALID_CHARS) {
+                        return true;
                     }
+
+                    // "\\?\" paths - but not "\\.\" paths - are passed
+                    // to the file system without validation
+
+                    0 != (parse_flags & IGNORE_INVALID_CHARS_IN_LONG_PATH)
+                        && 0 != Self::prefix_length(path)
+                        && '?' == path[2].as_char()
+                }
+
+                fn is_home_rooted(classification : Classification) -> bool {
+                    Classification::HomeRooted == classification
                 }
 
-                cr.Location = PoSl::new(0, cr.EntryName.offset);
+                fn name_is_reserved(name : &str) -> bool {
+                    // names that are altered by Windows, which removes any
+                    // trailing dots and spaces
+
+                    if "." != name && ".." != name && name.ends_with(['.', ' ']) {
+                        return true;
+                    }
+
+                    // device names, with or without an extension
+
+                    let base = name.split('.').next().unwrap_or(name).trim_end_matches(' ');
+
+                    if ["CON", "PRN", "AUX", "NUL", "CONIN$", "CONOUT$"]
+                        .iter()
+                        .any(|device| device.eq_ignore_ascii_case(base))
+                    {
+                        return true;
+                    }
+
+                    match (base.get(..3), base.get(3..)) {
+                        (Some(device), Some(number)) => {
+                            (device.eq_ignore_ascii_case("COM") || device.eq_ignore_ascii_case("LPT"))
+                                && matches!(
+                                    number,
+                                    "0" | "1"
+                                        | "2"
+                                        | "3"
+                                        | "4"
+                                        | "5"
+                                        | "6"
+                                        | "7"
+                                        | "8"
+                                        | "9"
+                                        | "\u{B9}"
+                                        | "\u{B2}"
+                                        | "\u{B3}"
+                                )
+                        },
+                        _ => false,
+                    }
+                }
 
-                (cl, cr)
+                fn char_fold_case(c : char) -> char {
+                    char_upcase(c)
+                }
             }
 
-            /// Examines the path to the degree necessary to be able to
-            /// classify it.
+
+            /// A Windows path together with its classification.
+            #[cfg(feature = "alloc")]
+            pub type ClassifiedPath<'a> = syntax::ClassifiedPath<'a, WindowsSyntax>;
+
+            /// The key of a Windows path, for use in a `HashMap` or `BTreeMap`.
+            #[cfg(feature = "alloc")]
+            pub type PathKey = syntax::PathKey<WindowsSyntax>;
+
+            /// Compact storage for large numbers of Windows paths.
+            #[cfg(feature = "alloc")]
+            pub type PathArena = syntax::PathArena<WindowsSyntax>;
+
+            /// A map from Windows paths to values that supports prefix queries.
+            #[cfg(feature = "alloc")]
+            pub type PathTrie<V> = syntax::PathTrie<V, WindowsSyntax>;
+
+
+            /// Classifies a Windows path.
             ///
             /// # Parameters:
             /// - `path` - the given path to be classified;
             /// - `parse_flags` - flags that moderate the classification;
+            pub fn path_classify(
+                path : &str,
+                parse_flags : i32,
+            ) -> (
+                Classification,       // classification
+                ClassificationResult, // classification_result
+            ) {
+                syntax::path_classify::<WindowsSyntax>(path, parse_flags)
+            }
+
+            /// Classifies a Windows path using byte-level searches, obtaining results
+            /// identical to those of [`path_classify()`].
             ///
-            /// # Returns:
-            /// `(classification : Classification, root : PositionalSlice, path_root_stripped : PositionalSlice)`
-            fn classify_root_(
+            /// See [`syntax::path_classify_fast()`] for details.
+            ///
+            /// # Parameters:
+            /// - `path` - the given path to be classified;
+            /// - `parse_flags` - flags that moderate the classification;
+            pub fn path_classify_fast(
                 path : &str,
                 parse_flags : i32,
             ) -> (
-                Classification, // classification
-                PoSl,           // root
-                PoSl,           // path_root_stripped
+                Classification,       // classification
+                ClassificationResult, // classification_result
             ) {
-                debug_assert!(!path.is_empty());
+                syntax::path_classify_fast::<WindowsSyntax>(path, parse_flags)
+            }
 
-                {
-                    let _ = parse_flags;
-                }
+            /// Classifies a Windows path expressed in UTF-16, such as is
+            /// obtained from the wide-character Windows APIs.
+            ///
+            /// The positions in the result are in `u16` units. Unpaired
+            /// surrogates are treated as ordinary name characters. Use
+            /// [`wide_to_string()`] to obtain a `String` from (a slice of) the
+            /// path where that is lossless.
+            ///
+            /// # Parameters:
+            /// - `path` - the given path to be classified;
+            /// - `parse_flags` - flags that moderate the classification;
+            pub fn path_classify_wide(
+                path : &[u16],
+                parse_flags : i32,
+            ) -> (
+                Classification,       // classification
+                ClassificationResult, // classification_result
+            ) {
+                // All characters of significance to the classification are
+                // in the BMP and are not surrogates, so never occur within a
+                // surrogate pair
 
-                let mut c0 : char = '\0';
-                let mut c1 : char = '\0';
-                let mut c2 : char;
+                syntax::path_classify_units::<WindowsSyntax, u16>(path, parse_flags)
+            }
 
-                let mut is_drive_2 = false;
+            /// Obtains the name of the WSL distribution of a path that has
+            /// been classified as [`WslRooted`](Classification::WslRooted),
+            /// e.g. `"Ubuntu"` for `"\\wsl.localhost\Ubuntu\home\me"`.
+            ///
+            /// # Parameters:
+            /// - `path` - the classified path;
+            /// - `classification` - the classification of `path`;
+            /// - `cr` - the classification result of `path`;
+            ///
+            /// # Returns:
+            /// `None` if `classification` is not
+            /// [`WslRooted`](Classification::WslRooted).
+            pub fn wsl_distro_name<'a>(
+                path : &'a str,
+                classification : Classification,
+                cr : &ClassificationResult,
+            ) -> Option<&'a str> {
+                if Classification::WslRooted != classification {
+                    return None;
+                }
 
-                let mut ix = -1;
-                for c in path.chars() {
-                    ix += 1;
+                let root = cr.Root.substring_of(path);
 
-                    match ix {
-                        0 => c0 = c,
-                        1 => c1 = c,
-                        2 => c2 = c,
-                        _ => (),
-                    }
+                root.rfind(['\\', '/']).map(|index| &root[index + 1..])
+            }
 
+            /// Obtains the UNIX path, within its WSL distribution, of a path
+            /// that has been classified as
+            /// [`WslRooted`](Classification::WslRooted), e.g.
+            /// `"/home/me"` for `"\\wsl.localhost\Ubuntu\home\me"`.
+            ///
+            /// # Parameters:
+            /// - `path` - the classified path;
+            /// - `classification` - the classification of `path`;
+            /// - `cr` - the classification result of `path`;
+            ///
+            /// # Returns:
+            /// `None` if `classification` is not
+            /// [`WslRooted`](Classification::WslRooted).
+            #[cfg(feature = "alloc")]
+            pub fn wsl_unix_path(
+                path : &str,
+                classification : Classification,
+                cr : &ClassificationResult,
+            ) -> Option<String> {
+                if Classification::WslRooted != classification {
+                    return None;
+                }
 
-                    if ix == 1 {
-                        if char_is_drive_letter_(c0) && ':' == c1 {
-                            is_drive_2 = true;
-                        }
+                let remainder = &path[cr.Root.offset + cr.Root.len()..];
 
-                        if '~' == c0 && char_is_path_name_separator_(c1) {
-                            return (
-                                // argument list:
-                                Classification::HomeRooted,
-                                PoSl::new(0, 1),
-                                PoSl::new(1, path.len() - 1),
-                            );
-                        }
-                    }
+                if remainder.is_empty() {
+                    return Some(String::from("/"));
+                }
 
-                    if ix == 2 {
-                        if is_drive_2 {
-                            let classification = if char_is_path_name_separator_(c) {
-                                Classification::DriveLetterRooted
-                            } else {
-                                Classification::DriveLetterRelative
-                            };
+                Some(remainder.replace('\\', "/"))
+            }
 
-                            return (
-                                // argument list:
-                                classification,
-                                PoSl::new(0, 2),
-                                PoSl::new(2, path.len() - 2),
-                            );
-                        }
-                    }
+            /// Obtains the upper-case form of a character in the manner of
+            /// the NTFS upcase table, which is used by Windows to compare
+            /// names case-insensitively.
+            ///
+            /// The simple (one-to-one) Unicode upper-case mapping is applied
+            /// to characters in the Basic Multilingual Plane, so that, for
+            /// example, `'ς'` and `'σ'` map to `'Σ'`, and the Turkish
+            /// dotless `'ı'` maps to `'I'`, while the dotted `'İ'` maps only
+            /// to itself. Characters whose upper-case form is not a single
+            /// character in the BMP - such as `'ß'`, whose full upper-case
+            /// form is `"SS"` - and characters outside of the BMP are
+            /// unchanged.
+            pub fn char_upcase(c : char) -> char {
+                if u32::from(c) > 0xFFFF {
+                    return c;
+                }
 
-                    if char_is_path_name_separator_(c) {
-                        if 0 == ix {
-                            return (
-                                // argument list:
-                                Classification::SlashRooted,
-                                PoSl::empty(),
-                                PoSl::new(0, path.len()),
-                            );
-                        }
+                let mut upper = c.to_uppercase();
 
-                        break;
-                    }
+                match (upper.next(), upper.next()) {
+                    (Some(u), None) if u32::from(u) <= 0xFFFF => u,
+                    _ => c,
                 }
+            }
 
-                if 0 == ix && '~' == c0 {
-                    return (
-                        // argument list:
-                        Classification::HomeRooted,
-                        PoSl::new(0, 1),
-                        PoSl::new(1, path.len() - 1),
-                    );
-                }
+            /// Evaluates whether two names are equal when compared
+            /// case-insensitively in the manner of NTFS, i.e. after
+            /// applying [`char_upcase()`] to each of their characters.
+            pub fn names_equal_ignore_case(
+                lhs : &str,
+                rhs : &str,
+            ) -> bool {
+                lhs.chars().map(char_upcase).eq(rhs.chars().map(char_upcase))
+            }
 
-                (
-                    // argument list:
-                    Classification::Relative,
-                    PoSl::empty(),
-                    PoSl::new(0, path.len()),
-                )
+            /// Obtains the longest common ancestor directory of a number
+            /// of Windows paths.
+            ///
+            /// See [`syntax::common_ancestor()`] for details.
+            pub fn common_ancestor<'a>(paths : impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+                syntax::common_ancestor::<WindowsSyntax, _>(paths)
             }
 
-            /// Evaluates whether a character is a path-name-separator.
-            fn char_is_path_name_separator_(c : char) -> bool {
-                match c {
-                    '/' => true,
-                    '\\' => true,
-                    _ => false,
-                }
+            /// Normalises a Windows path lexically.
+            ///
+            /// See [`syntax::normalise()`] for details.
+            #[cfg(feature = "alloc")]
+            pub fn normalise(path : &str) -> String {
+                syntax::normalise::<WindowsSyntax>(path)
             }
 
-            /// Looks for the last slash (forward or backward) in the slice.
-            fn find_last_slash_(s : &str) -> Option<usize> {
-                // TODO: consider rfind(&['/', '\\'][..])
+            /// Determines, lexically, whether the Windows path `candidate` is
+            /// contained within the directory `base`, obtaining their
+            /// normalised join if it is.
+            ///
+            /// See [`syntax::is_contained_within()`] for details.
+            #[cfg(feature = "alloc")]
+            pub fn is_contained_within(
+                base : &str,
+                candidate : &str,
+            ) -> Result<String, syntax::ContainmentError> {
+                syntax::is_contained_within::<WindowsSyntax>(base, candidate)
+            }
 
-                let last_fslash = s.rfind('/');
+            /// Joins the Windows path `path` to the directory `base`.
+            ///
+            /// See [`syntax::join()`] for details.
+            #[cfg(feature = "alloc")]
+            pub fn join(
+                base : &str,
+                path : &str,
+            ) -> String {
+                syntax::join::<WindowsSyntax>(base, path)
+            }
 
-                match last_fslash {
-                    Some(index1) => {
-                        let last_rslash = s[index1 + 1..].rfind('\\');
+            /// Obtains the Windows path that identifies `path` relative to the
+            /// directory `base`.
+            ///
+            /// See [`syntax::relative_path()`] for details.
+            #[cfg(feature = "alloc")]
+            pub fn relative_path(
+                base : &str,
+                path : &str,
+            ) -> Result<String, syntax::RelativePathError> {
+                syntax::relative_path::<WindowsSyntax>(base, path)
+            }
 
-                        match last_rslash {
-                            Some(index2) => {
-                                if index2 > index1 {
-                                    Some(index2)
-                                } else {
-                                    Some(index1)
-                                }
-                            },
-                            None => last_fslash,
-                        }
-                    },
-                    None => s.rfind('\\'),
-                }
+            /// Evaluates whether two Windows paths are equal.
+            ///
+            /// See [`syntax::paths_equal()`] for details.
+            #[cfg(feature = "alloc")]
+            pub fn paths_equal(
+                lhs : &str,
+                rhs : &str,
+                comparison_flags : i32,
+            ) -> bool {
+                syntax::paths_equal::<WindowsSyntax>(lhs, rhs, comparison_flags)
             }
 
-            fn count_directory_parts_(
-                s : &str,
-                parse_flags : i32,
+            /// Compares two Windows paths.
+            ///
+            /// See [`syntax::path_cmp()`] for details.
+            #[cfg(feature = "alloc")]
+            pub fn path_cmp(
+                lhs : &str,
+                rhs : &str,
+                comparison_flags : i32,
+            ) -> core::cmp::Ordering {
+                syntax::path_cmp::<WindowsSyntax>(lhs, rhs, comparison_flags)
+            }
+
+            /// Obtains the key of a Windows path, for use in a `HashMap` or
+            /// `BTreeMap`.
+            ///
+            /// See [`syntax::path_key()`] for details.
+            #[cfg(feature = "alloc")]
+            pub fn path_key(
+                path : &str,
+                comparison_flags : i32,
+            ) -> PathKey {
+                syntax::path_key::<WindowsSyntax>(path, comparison_flags)
+            }
+
+            /// Applies a Unicode normal form to each name in a Windows
+            /// path.
+            ///
+            /// See [`syntax::normalise_unicode()`] for details.
+            #[cfg(feature = "unicode")]
+            pub fn normalise_unicode(
+                path : &str,
+                form : syntax::NormalForm,
+            ) -> String {
+                syntax::normalise_unicode::<WindowsSyntax>(path, form)
+            }
+
+            /// Evaluates whether each name in a Windows path is in a
+            /// Unicode normal form.
+            ///
+            /// See [`syntax::is_unicode_normalised()`] for details.
+            #[cfg(feature = "unicode")]
+            pub fn is_unicode_normalised(
+                path : &str,
+                form : syntax::NormalForm,
+            ) -> bool {
+                syntax::is_unicode_normalised::<WindowsSyntax>(path, form)
+            }
+
+            /// Converts a UTF-16 path, or slice thereof, into a `String`,
+            /// if it can be done losslessly.
+            ///
+            /// # Returns:
+            /// `None` if `path` contains any unpaired surrogates.
+            #[cfg(feature = "alloc")]
+            pub fn wide_to_string(path : &[u16]) -> Option<String> {
+                String::from_utf16(path).ok()
+            }
+
+            /// Classifies the root of a UNC path, whose server name begins
+            /// at `server_start`, as either
+            /// [`UncRooted`](Classification::UncRooted), in which case the
+            /// root comprises the server and share names, or
+            /// [`UncIncomplete`](Classification::UncIncomplete), in which
+            /// case one or both of the names is missing.
+            fn classify_unc_root_<U : CodeUnit>(
+                path : &[U],
+                server_start : usize,
             ) -> (
-                usize, // number_of_parts
-                usize, // number_of_dots_parts
+                Classification, // classification
+                PoSl,           // root
+                PoSl,           // path_root_stripped
             ) {
-                {
-                    let _ = parse_flags;
-                }
+                let len = path.len();
 
-                // This function counts the number of directory parts and the
-                // number of those that are dots directories
+                let server_end = find_next_slash_(path, server_start);
 
-                let mut number_of_parts = 0usize;
-                let mut number_of_dots_parts = 0usize;
+                if server_end == server_start || server_end == len {
+                    return (
+                        // argument list:
+                        Classification::UncIncomplete,
+                        PoSl::new(0, server_end),
+                        PoSl::new(server_end, len - server_end),
+                    );
+                }
 
-                let mut prev = 'X';
+                let share_start = server_end + 1;
+                let share_end = find_next_slash_(path, share_start);
 
-                let mut num_dots = 0;
+                if share_end == share_start {
+                    return (
+                        // argument list:
+                        Classification::UncIncomplete,
+                        PoSl::new(0, share_start),
+                        PoSl::new(share_start, len - share_start),
+                    );
+                }
 
-                for c in s.chars() {
-                    if char_is_path_name_separator_(c) {
-                        match num_dots {
-                            1 | 2 => number_of_dots_parts += 1,
-                            _ => (),
-                        }
+                let server = &path[server_start..server_end];
 
-                        if char_is_path_name_separator_(prev) {
-                        } else {
-                            number_of_parts += 1;
-                        }
+                let classification = if unit_str_eq_ignore_ascii_case_(server, "wsl$")
+                    || unit_str_eq_ignore_ascii_case_(server, "wsl.localhost")
+                {
+                    Classification::WslRooted
+                } else {
+                    Classification::UncRooted
+                };
 
-                        num_dots = 0;
-                    } else {
-                        if '.' == c {
-                            num_dots += 1;
-                        } else {
-                            num_dots += 100;
-                        }
-                    }
+                (
+                    // argument list:
+                    classification,
+                    PoSl::new(0, share_end),
+                    PoSl::new(share_end, len - share_end),
+                )
+            }
 
-                    prev = c;
-                }
+            /// Evaluates whether a sequence of code units is the same as the
+            /// given ASCII string, ignoring case.
+            fn unit_str_eq_ignore_ascii_case_<U : CodeUnit>(
+                s : &[U],
+                ascii : &str,
+            ) -> bool {
+                s.len() == ascii.len()
+                    && s.iter()
+                        .zip(ascii.chars())
+                        .all(|(&u, ch)| ch.eq_ignore_ascii_case(&u.as_char()))
+            }
 
-                (number_of_parts, number_of_dots_parts)
+            /// Obtains the index of the first path-name separator at or
+            /// after `from`, or the length of the path if there is none.
+            fn find_next_slash_<U : CodeUnit>(
+                path : &[U],
+                from : usize,
+            ) -> usize {
+                path[from..]
+                    .iter()
+                    .position(|&u| WindowsSyntax::char_is_path_name_separator(u.as_char()))
+                    .map_or(path.len(), |index| from + index)
             }
 
             /// Indicates whether the given character is a drive letter.
             fn char_is_drive_letter_(c : char) -> bool {
-                match c {
-                    'A'..='Z' => true,
-                    'a'..='z' => true,
-                    _ => false,
-                }
+                c.is_ascii_alphabetic()
             }
 
 
@@ -769,14 +1552,13 @@ pub mod libpath {
 
                 use super::{
                     char_is_drive_letter_,
-                    char_is_path_name_separator_,
                     classification_flags,
-                    classify_root_,
-                    count_directory_parts_,
                     Classification,
+                    PathSyntax,
+                    WindowsSyntax,
                 };
 
-                use fastparse::fastparse::types::PositionalSlice as PoSl;
+                use crate::libpath::util::common::PositionalSlice as PoSl;
 
 
                 #[test]
@@ -795,28 +1577,84 @@ pub mod libpath {
 
                 #[test]
                 fn TEST_char_is_path_name_separator__1() {
-                    assert!(char_is_path_name_separator_('/'));
-                    assert!(char_is_path_name_separator_('\\'));
+                    assert!(WindowsSyntax::char_is_path_name_separator('/'));
+                    assert!(WindowsSyntax::char_is_path_name_separator('\\'));
 
-                    assert!(!char_is_path_name_separator_('-'));
-                    assert!(!char_is_path_name_separator_(';'));
-                    assert!(!char_is_path_name_separator_(':'));
-                    assert!(!char_is_path_name_separator_('a'));
+                    assert!(!WindowsSyntax::char_is_path_name_separator('-'));
+                    assert!(!WindowsSyntax::char_is_path_name_separator(';'));
+                    assert!(!WindowsSyntax::char_is_path_name_separator(':'));
+                    assert!(!WindowsSyntax::char_is_path_name_separator('a'));
                 }
 
                 #[test]
-                fn TEST_classify_root__1() {
-                }
+                fn TEST_char_is_invalid__1() {
+                    for c in ['<', '>', ':', '"', '|', '?', '*', '\0', '\t', '\x1F'] {
+                        assert!(WindowsSyntax::char_is_invalid(c), "c={c:?}");
+                    }
 
-                #[test]
-                fn TEST_count_directory_parts__1() {
+                    for c in ['a', ' ', '.', '-', '\\', '/', '\x7F', 'é'] {
+                        assert!(!WindowsSyntax::char_is_invalid(c), "c={c:?}");
+                    }
                 }
 
                 #[test]
-                fn TEST_find_last_slash__1() {
+                fn TEST_classify_root__1() {
+                    let parse_flags = classification_flags::RECOGNISE_TILDE_HOME;
+
+                    assert_eq!(
+                        (Classification::Relative, PoSl::empty(), PoSl::new(0, 3)),
+                        WindowsSyntax::classify_root(b"abc", parse_flags)
+                    );
+                    assert_eq!(
+                        (Classification::SlashRooted, PoSl::empty(), PoSl::new(0, 4)),
+                        WindowsSyntax::classify_root(b"/abc", parse_flags)
+                    );
+                    assert_eq!(
+                        (Classification::SlashRooted, PoSl::empty(), PoSl::new(0, 4)),
+                        WindowsSyntax::classify_root(b"\\abc", parse_flags)
+                    );
+                    assert_eq!(
+                        (Classification::DriveLetterRooted, PoSl::new(0, 2), PoSl::new(2, 4)),
+                        WindowsSyntax::classify_root(b"C:\\abc", parse_flags)
+                    );
+                    assert_eq!(
+                        (Classification::DriveLetterRelative, PoSl::new(0, 2), PoSl::new(2, 3)),
+                        WindowsSyntax::classify_root(b"C:abc", parse_flags)
+                    );
+                    assert_eq!(
+                        (Classification::DriveLetterRelative, PoSl::new(0, 2), PoSl::new(2, 0)),
+                        WindowsSyntax::classify_root(b"C:", parse_flags)
+                    );
+                    assert_eq!(
+                        (Classification::HomeRooted, PoSl::new(0, 1), PoSl::new(1, 4)),
+                        WindowsSyntax::classify_root(b"~\\abc", parse_flags)
+                    );
                 }
             }
         }
+
+
+        pub mod syntax;
+
+        pub mod detect;
+
+        #[cfg(feature = "alloc")]
+        pub mod archive;
+
+        #[cfg(feature = "alloc")]
+        pub mod batch;
+
+        #[cfg(feature = "unicode")]
+        pub mod collisions;
+
+        #[cfg(feature = "alloc")]
+        pub mod convert;
+
+        #[cfg(feature = "std")]
+        pub mod native;
+
+        #[cfg(feature = "alloc")]
+        pub mod portability;
     }
 
 
@@ -835,20 +1673,23 @@ pub mod libpath {
 #[allow(non_snake_case)]
 mod tests {
     use crate::libpath::util::{
-        common::ClassificationResult,
+        common::{
+            ClassificationResult,
+            PositionalSlice as PoSl,
+        },
         // unix::*,
         // windows::*,
     };
 
-    use fastparse::fastparse::types::PositionalSlice as PoSl;
-
 
     #[allow(non_snake_case)]
     mod unix {
 
         use crate::libpath::util::unix::{
             classification_flags::*,
+            foreign_drive_letter,
             path_classify,
+            path_classify_bytes,
             Classification,
         };
 
@@ -857,7 +1698,7 @@ mod tests {
 
         #[test]
         fn TEST_path_classify_WITH_EMPTY_INPUT() {
-            let flag_max = 0 | IGNORE_SLASH_RUNS | IGNORE_INVALID_CHARS | RECOGNISE_TILDE_HOME;
+            let flag_max = IGNORE_SLASH_RUNS | IGNORE_INVALID_CHARS | RECOGNISE_TILDE_HOME;
 
             for flags in 0..=flag_max {
                 let (cl, cr) = path_classify("", flags);
@@ -1370,6 +2211,175 @@ mod tests {
             assert_eq!("", cr.Stem.substring_of(path));
             assert_eq!("", cr.Extension.substring_of(path));
         }
+
+        #[test]
+        fn TEST_path_classify_bytes_SAME_AS_path_classify() {
+            let paths = [
+                "",
+                "name.ext",
+                ".ext",
+                "...",
+                "dir/name.ext",
+                "dir1/../",
+                "/dir/sub-dir/file.ext",
+                "//dir//file",
+                "~",
+                "~/dir/sub-dir/file.ext",
+                "~name",
+                "/dïr/fïlé.ëxt",
+            ];
+
+            for path in paths {
+                assert_eq!(
+                    path_classify(path, 0),
+                    path_classify_bytes(path.as_bytes(), 0),
+                    "path='{path}'"
+                );
+            }
+        }
+
+        #[test]
+        fn TEST_path_classify_bytes_WITH_INVALID_UTF8() {
+            let path = b"/dir\xC0/sub-dir/\xFFfile.e\xFEt";
+            let parse_flags : i32 = 0;
+            let (cl, cr) = path_classify_bytes(path, parse_flags);
+
+            assert_eq!(Classification::SlashRooted, cl);
+
+            assert_eq!(PoSl::new(0, 23), cr.Input);
+            assert_eq!(PoSl::new(0, 14), cr.Location);
+            assert_eq!(PoSl::empty(), cr.Root);
+            assert_eq!(PoSl::new(0, 14), cr.Directory);
+            assert_eq!(3, cr.NumDirectoryParts);
+            assert_eq!(0, cr.NumDotsDirectoryParts);
+            assert_eq!(PoSl::new(14, 9), cr.EntryName);
+            assert_eq!(PoSl::new(14, 5), cr.Stem);
+            assert_eq!(PoSl::new(19, 4), cr.Extension);
+
+            assert_eq!(b"/dir\xC0/sub-dir/", cr.Directory.subslice_of(path));
+            assert_eq!(b"\xFFfile", cr.Stem.subslice_of(path));
+            assert_eq!(b".e\xFEt", cr.Extension.subslice_of(path));
+        }
+
+        #[cfg(feature = "std")]
+        #[test]
+        fn TEST_path_classify_os_str() {
+            use crate::libpath::util::unix::path_classify_os_str;
+            use std::ffi::OsStr;
+
+            let path = OsStr::new("~/dir/file.ext");
+            let (cl, cr) = path_classify_os_str(path, 0);
+
+            assert_eq!(Classification::HomeRooted, cl);
+            assert_eq!(b"file.ext", cr.EntryName.subslice_of(path.as_encoded_bytes()));
+        }
+
+        #[cfg(all(unix, feature = "std"))]
+        #[test]
+        fn TEST_path_classify_os_str_WITH_NON_UNICODE() {
+            use crate::libpath::util::unix::path_classify_os_str;
+            use std::{
+                ffi::OsStr,
+                os::unix::ffi::OsStrExt,
+            };
+
+            let path = OsStr::from_bytes(b"dir/\x80\x81.ext");
+            let (cl, cr) = path_classify_os_str(path, 0);
+
+            assert_eq!(Classification::Relative, cl);
+            assert_eq!(PoSl::new(4, 6), cr.EntryName);
+            assert_eq!(PoSl::new(6, 4), cr.Extension);
+        }
+
+        #[test]
+        fn TEST_path_classify_WITH_INVALID_CHARS() {
+            let path = "/dir/fi\0le.ext";
+            let (cl, cr) = path_classify(path, 0);
+
+            assert_eq!(Classification::InvalidChars, cl);
+
+            assert_eq!(PoSl::new(7, 1), cr.FirstInvalid);
+            assert_eq!("fi\0le.ext", cr.EntryName.substring_of(path));
+
+            let (cl, cr) = path_classify(path, IGNORE_INVALID_CHARS);
+
+            assert_eq!(Classification::SlashRooted, cl);
+
+            assert_eq!(PoSl::new(7, 1), cr.FirstInvalid);
+
+            // characters that are invalid only on Windows
+
+            for path in ["a?b", "file.txt:stream", r"dir\a|b", r"dir\file?.ext"] {
+                let (cl, cr) = path_classify(path, 0);
+
+                assert_eq!(Classification::Relative, cl, "path='{path}'");
+                assert!(cr.FirstInvalid.is_empty(), "path='{path}'");
+            }
+        }
+
+        #[test]
+        fn TEST_path_classify_WITH_ForeignDriveRooted_PATH() {
+            let cases = [
+                ("/mnt/c/Users/file.ext", RECOGNISE_WSL_DRIVES, "/mnt/c", "/Users/", 'c'),
+                (
+                    "/cygdrive/d/Users/file.ext",
+                    RECOGNISE_CYGWIN_DRIVES,
+                    "/cygdrive/d",
+                    "/Users/",
+                    'd',
+                ),
+                ("/c/Users/file.ext", RECOGNISE_MSYS_DRIVES, "/c", "/Users/", 'c'),
+                (
+                    "/mnt/c/Users/file.ext",
+                    RECOGNISE_FOREIGN_DRIVES,
+                    "/mnt/c",
+                    "/Users/",
+                    'c',
+                ),
+            ];
+
+            for (path, parse_flags, expected_root, expected_directory, expected_drive) in cases {
+                let (cl, cr) = path_classify(path, parse_flags);
+
+                assert_eq!(Classification::ForeignDriveRooted, cl, "path='{path}'");
+
+                assert_eq!(expected_root, cr.Root.substring_of(path), "path='{path}'");
+                assert_eq!(expected_directory, cr.Directory.substring_of(path), "path='{path}'");
+                assert_eq!(2, cr.NumDirectoryParts, "path='{path}'");
+                assert_eq!("file.ext", cr.EntryName.substring_of(path), "path='{path}'");
+                assert_eq!(
+                    Some(expected_drive),
+                    foreign_drive_letter(path, cl, &cr),
+                    "path='{path}'"
+                );
+            }
+
+            {
+                let path = "/mnt/c";
+                let (cl, cr) = path_classify(path, RECOGNISE_WSL_DRIVES);
+
+                assert_eq!(Classification::ForeignDriveRooted, cl);
+
+                assert_eq!("/mnt/c", cr.Root.substring_of(path));
+                assert_eq!("", cr.Directory.substring_of(path));
+                assert_eq!("", cr.EntryName.substring_of(path));
+            }
+        }
+
+        #[test]
+        fn TEST_path_classify_WITHOUT_RECOGNISE_FOREIGN_DRIVES() {
+            for path in [
+                "/mnt/c/Users/file.ext",
+                "/cygdrive/c/Users/file.ext",
+                "/c/Users/file.ext",
+            ] {
+                let (cl, cr) = path_classify(path, 0);
+
+                assert_eq!(Classification::SlashRooted, cl, "path='{path}'");
+                assert_eq!(PoSl::empty(), cr.Root, "path='{path}'");
+                assert_eq!(None, foreign_drive_letter(path, cl, &cr), "path='{path}'");
+            }
+        }
     }
 
 
@@ -1377,8 +2387,11 @@ mod tests {
     mod windows {
 
         use crate::libpath::util::windows::{
+            char_upcase,
             classification_flags::*,
+            names_equal_ignore_case,
             path_classify,
+            wsl_distro_name,
             Classification,
         };
 
@@ -1388,7 +2401,7 @@ mod tests {
         #[test]
         fn TEST_path_classify_WITH_EMPTY_INPUT() {
             let flag_max =
-                0 | IGNORE_SLASH_RUNS | IGNORE_INVALID_CHARS | RECOGNISE_TILDE_HOME | IGNORE_INVALID_CHARS_IN_LONG_PATH;
+                IGNORE_SLASH_RUNS | IGNORE_INVALID_CHARS | RECOGNISE_TILDE_HOME | IGNORE_INVALID_CHARS_IN_LONG_PATH;
 
             for flags in 0..=flag_max {
                 let (cl, cr) = path_classify("", flags);
@@ -1996,6 +3009,121 @@ mod tests {
             }
         }
 
+        #[test]
+        fn TEST_path_classify_WITH_DriveLetterRelative_DRIVE_ONLY() {
+            let path = "C:";
+            let parse_flags : i32 = 0;
+            let (cl, cr) = path_classify(path, parse_flags);
+
+            assert_eq!(Classification::DriveLetterRelative, cl);
+
+            assert_eq!(PoSl::new(0, 2), cr.Input);
+            assert_eq!(PoSl::new(0, 2), cr.Location);
+            assert_eq!(PoSl::new(0, 2), cr.Root);
+            assert_eq!(PoSl::new(2, 0), cr.Directory);
+            assert_eq!(0, cr.NumDirectoryParts);
+            assert_eq!(PoSl::new(2, 0), cr.EntryName);
+        }
+
+        #[test]
+        fn TEST_path_classify_WITH_ENTRY_NAME_DOT_AND_ONE_CHARACTER() {
+            let path = r"dir\.a";
+            let parse_flags : i32 = 0;
+            let (cl, cr) = path_classify(path, parse_flags);
+
+            assert_eq!(Classification::Relative, cl);
+
+            assert_eq!(".a", cr.EntryName.substring_of(path));
+            assert_eq!("", cr.Stem.substring_of(path));
+            assert_eq!(".a", cr.Extension.substring_of(path));
+        }
+
+        #[cfg(feature = "alloc")]
+        #[test]
+        fn TEST_path_classify_wide_SAME_AS_path_classify() {
+            use crate::libpath::util::windows::{
+                path_classify_wide,
+                wide_to_string,
+            };
+
+            let paths = [
+                "",
+                "name.ext",
+                ".ext",
+                "..",
+                r"dir\name.ext",
+                "dir1/../",
+                r"\dir\sub-dir/file.ext",
+                "C:",
+                r"C:\dir\file.ext",
+                "C:dir/file.ext",
+                r"~\dir\file.ext",
+                "~",
+                r"\dïr\fïlé.ëxt",
+                r"\日本\語.テキスト",
+                "\u{1F600}\\\u{1F600}.\u{1F600}",
+            ];
+
+            for path in paths {
+                let wide : Vec<u16> = path.encode_utf16().collect();
+
+                let (cl_s, cr_s) = path_classify(path, 0);
+                let (cl_w, cr_w) = path_classify_wide(&wide, 0);
+
+                assert_eq!(cl_s, cl_w, "path='{path}'");
+
+                for (ps, pw) in [
+                    (cr_s.Root, cr_w.Root),
+                    (cr_s.Directory, cr_w.Directory),
+                    (cr_s.EntryName, cr_w.EntryName),
+                    (cr_s.Stem, cr_w.Stem),
+                    (cr_s.Extension, cr_w.Extension),
+                ] {
+                    assert_eq!(
+                        Some(ps.substring_of(path)),
+                        wide_to_string(pw.subslice_of(&wide)).as_deref(),
+                        "path='{path}'"
+                    );
+                }
+
+                assert_eq!(cr_s.NumDirectoryParts, cr_w.NumDirectoryParts, "path='{path}'");
+                assert_eq!(cr_s.NumDotsDirectoryParts, cr_w.NumDotsDirectoryParts, "path='{path}'");
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        #[test]
+        fn TEST_path_classify_wide_WITH_LONE_SURROGATES() {
+            use crate::libpath::util::windows::{
+                path_classify_wide,
+                wide_to_string,
+            };
+
+            // C:\dir<high-surrogate>\<low-surrogate>file.e<high-surrogate>t
+            let path : &[u16] = &[
+                0x43, 0x3A, 0x5C, 0x64, 0x69, 0x72, 0xD800, 0x5C, 0xDC00, 0x66, 0x69, 0x6C, 0x65, 0x2E, 0x65, 0xD801,
+                0x74,
+            ];
+            let parse_flags : i32 = 0;
+            let (cl, cr) = path_classify_wide(path, parse_flags);
+
+            assert_eq!(Classification::DriveLetterRooted, cl);
+
+            assert_eq!(PoSl::new(0, 17), cr.Input);
+            assert_eq!(PoSl::new(0, 8), cr.Location);
+            assert_eq!(PoSl::new(0, 2), cr.Root);
+            assert_eq!(PoSl::new(2, 6), cr.Directory);
+            assert_eq!(2, cr.NumDirectoryParts);
+            assert_eq!(0, cr.NumDotsDirectoryParts);
+            assert_eq!(PoSl::new(8, 9), cr.EntryName);
+            assert_eq!(PoSl::new(8, 5), cr.Stem);
+            assert_eq!(PoSl::new(13, 4), cr.Extension);
+
+            assert_eq!(Some("C:".to_string()), wide_to_string(cr.Root.subslice_of(path)));
+            assert_eq!(None, wide_to_string(cr.Directory.subslice_of(path)));
+            assert_eq!(None, wide_to_string(cr.Stem.subslice_of(path)));
+        }
+
         #[test]
         fn TEST_path_classify_WITH_HomeRooted_PATH_WITH__RECOGNISE_TILDE_HOME() {
             let path = "~/dir/sub-dir/file.ext";
@@ -2057,6 +3185,397 @@ mod tests {
             assert_eq!("", cr.Stem.substring_of(path));
             assert_eq!("", cr.Extension.substring_of(path));
         }
+
+        #[test]
+        fn TEST_path_classify_WITH_INVALID_CHARS() {
+            let path = r"C:\dir\file?.ext";
+            let (cl, cr) = path_classify(path, 0);
+
+            assert_eq!(Classification::InvalidChars, cl);
+
+            assert_eq!(PoSl::new(11, 1), cr.FirstInvalid);
+            assert_eq!(r"\dir\", cr.Directory.substring_of(path));
+            assert_eq!("file?.ext", cr.EntryName.substring_of(path));
+
+            let (cl, cr) = path_classify(path, IGNORE_INVALID_CHARS);
+
+            assert_eq!(Classification::DriveLetterRooted, cl);
+
+            assert_eq!(PoSl::new(11, 1), cr.FirstInvalid);
+
+            let cases = [("a?b", 1), ("file.txt:stream", 8), (r"dir\a|b", 5), ("dir\\fi\0le", 6)];
+
+            for (path, index) in cases {
+                let (cl, cr) = path_classify(path, 0);
+
+                assert_eq!(Classification::InvalidChars, cl, "path='{path}'");
+                assert_eq!(PoSl::new(index, 1), cr.FirstInvalid, "path='{path}'");
+
+                let (cl, _) = path_classify(path, IGNORE_INVALID_CHARS);
+
+                assert_eq!(Classification::Relative, cl, "path='{path}'");
+            }
+        }
+
+        #[test]
+        fn TEST_path_classify_WITH_INVALID_CHARS_IN_LONG_PATH() {
+            let cases = [
+                (r"\\?\C:\dir\file?.ext", Classification::DriveLetterRooted),
+                (r"\\?\UNC\server\share\a|b", Classification::UncRooted),
+                (r"\\?\device\a<b", Classification::DeviceRooted),
+            ];
+
+            for (path, classification) in cases {
+                let (cl, _) = path_classify(path, 0);
+
+                assert_eq!(Classification::InvalidChars, cl, "path='{path}'");
+
+                let (cl, _) = path_classify(path, IGNORE_INVALID_CHARS_IN_LONG_PATH);
+
+                assert_eq!(classification, cl, "path='{path}'");
+            }
+
+            // the flag does not apply to paths without the "\\?\" prefix
+
+            for path in [r"C:\dir\file?.ext", r"\\.\device\a<b", r"\\server\share\a|b", "a?b"] {
+                let (cl, cr) = path_classify(path, IGNORE_INVALID_CHARS_IN_LONG_PATH);
+
+                assert_eq!(Classification::InvalidChars, cl, "path='{path}'");
+                assert!(!cr.FirstInvalid.is_empty(), "path='{path}'");
+
+                let (cl, _) = path_classify(path, IGNORE_INVALID_CHARS);
+
+                assert_ne!(Classification::InvalidChars, cl, "path='{path}'");
+            }
+        }
+
+        #[test]
+        fn TEST_path_classify_WITH_MIXED_SEPARATORS() {
+            let cases = [
+                (r"abc/def\ghi.ext", r"abc/def\", 2, "ghi.ext"),
+                (r"a/bcdef\g", r"a/bcdef\", 2, "g"),
+                (r"C:\dir/sub-dir\file", r"\dir/sub-dir\", 3, "file"),
+                (r"dir\sub-dir/", r"dir\sub-dir/", 2, ""),
+            ];
+
+            for (path, directory, num_directory_parts, entry_name) in cases {
+                let (_, cr) = path_classify(path, 0);
+
+                assert_eq!(directory, cr.Directory.substring_of(path), "path='{path}'");
+                assert_eq!(num_directory_parts, cr.NumDirectoryParts, "path='{path}'");
+                assert_eq!(entry_name, cr.EntryName.substring_of(path), "path='{path}'");
+            }
+        }
+
+        #[test]
+        fn TEST_path_classify_WITH_UncRooted_PATH() {
+            for path in [r"\\server\share\dir\file.ext", "//server/share/dir/file.ext"] {
+                let (cl, cr) = path_classify(path, 0);
+
+                assert_eq!(Classification::UncRooted, cl);
+
+                assert_eq!(PoSl::new(0, 27), cr.Input);
+                assert_eq!(PoSl::empty(), cr.Prefix);
+                assert_eq!(PoSl::new(0, 19), cr.Location);
+                assert_eq!(PoSl::new(0, 14), cr.Root);
+                assert_eq!(PoSl::new(14, 5), cr.Directory);
+                assert_eq!(2, cr.NumDirectoryParts);
+                assert_eq!(0, cr.NumDotsDirectoryParts);
+                assert_eq!(PoSl::new(19, 8), cr.EntryName);
+                assert_eq!(PoSl::new(19, 4), cr.Stem);
+                assert_eq!(PoSl::new(23, 4), cr.Extension);
+                assert!(cr.FirstInvalid.is_empty());
+            }
+
+            {
+                let path = r"\\server\share";
+                let (cl, cr) = path_classify(path, 0);
+
+                assert_eq!(Classification::UncRooted, cl);
+
+                assert_eq!(r"\\server\share", cr.Root.substring_of(path));
+                assert_eq!("", cr.Directory.substring_of(path));
+                assert_eq!("", cr.EntryName.substring_of(path));
+            }
+        }
+
+        #[test]
+        fn TEST_path_classify_WITH_UNC_PATH_IS_NOT_SlashRooted() {
+            // formerly, a path beginning with two separators was classified
+            // as SlashRooted, with an empty root
+
+            for path in [r"\\server\share", "//server/share", r"\/server\share"] {
+                let (cl, cr) = path_classify(path, 0);
+
+                assert_eq!(Classification::UncRooted, cl, "path='{path}'");
+                assert_eq!(path, cr.Root.substring_of(path), "path='{path}'");
+                assert_eq!(0, cr.NumDirectoryParts, "path='{path}'");
+            }
+
+            for path in [r"\server\share", "/server/share"] {
+                let (cl, cr) = path_classify(path, 0);
+
+                assert_eq!(Classification::SlashRooted, cl, "path='{path}'");
+                assert_eq!("", cr.Root.substring_of(path), "path='{path}'");
+                assert_eq!(2, cr.NumDirectoryParts, "path='{path}'");
+            }
+        }
+
+        #[test]
+        fn TEST_path_classify_WITH_UncIncomplete_PATH() {
+            let cases = [
+                (r"\\", r"\\", ""),
+                (r"\\server", r"\\server", ""),
+                (r"\\server\", r"\\server\", ""),
+                (r"\\server\\file.ext", r"\\server\", "file.ext"),
+                (r"\\\share", r"\\", "share"),
+            ];
+
+            for (path, expected_root, expected_entry_name) in cases {
+                let (cl, cr) = path_classify(path, 0);
+
+                assert_eq!(Classification::UncIncomplete, cl, "path='{path}'");
+                assert_eq!(expected_root, cr.Root.substring_of(path), "path='{path}'");
+                assert_eq!(expected_entry_name, cr.EntryName.substring_of(path), "path='{path}'");
+            }
+        }
+
+        #[test]
+        fn TEST_path_classify_WITH_LONG_PATH_PREFIX() {
+            {
+                let path = r"\\?\C:\dir\file.ext";
+                let (cl, cr) = path_classify(path, 0);
+
+                assert_eq!(Classification::DriveLetterRooted, cl);
+
+                assert_eq!(r"\\?\", cr.Prefix.substring_of(path));
+                assert_eq!(r"\\?\C:", cr.Root.substring_of(path));
+                assert_eq!(r"\dir\", cr.Directory.substring_of(path));
+                assert_eq!("file.ext", cr.EntryName.substring_of(path));
+                assert!(cr.FirstInvalid.is_empty());
+            }
+
+            for (path, expected_prefix) in [
+                (r"\\?\UNC\server\share\dir\file.ext", r"\\?\UNC\"),
+                (r"\\.\UNC\server\share\dir\file.ext", r"\\.\UNC\"),
+                (r"\\?\unc\server\share\dir\file.ext", r"\\?\unc\"),
+            ] {
+                let (cl, cr) = path_classify(path, 0);
+
+                assert_eq!(Classification::UncRooted, cl, "path='{path}'");
+
+                assert_eq!(expected_prefix, cr.Prefix.substring_of(path), "path='{path}'");
+                assert_eq!(
+                    format!(r"{expected_prefix}server\share"),
+                    cr.Root.substring_of(path),
+                    "path='{path}'"
+                );
+                assert_eq!(r"\dir\", cr.Directory.substring_of(path), "path='{path}'");
+                assert_eq!("file.ext", cr.EntryName.substring_of(path), "path='{path}'");
+            }
+
+            for path in [r"\\?\UNC\server", r"\\.\UNC\server\"] {
+                let (cl, _) = path_classify(path, 0);
+
+                assert_eq!(Classification::UncIncomplete, cl, "path='{path}'");
+            }
+        }
+
+        #[test]
+        fn TEST_path_classify_WITH_DeviceRooted_PATH() {
+            let cases = [
+                (r"\\.\COM1", r"\\.\", r"\\.\COM1", ""),
+                (r"\\.\pipe\name", r"\\.\", r"\\.\pipe", "name"),
+                (
+                    r"\\?\Volume{01234567-89ab}\dir\file.ext",
+                    r"\\?\",
+                    r"\\?\Volume{01234567-89ab}",
+                    "file.ext",
+                ),
+            ];
+
+            for (path, expected_prefix, expected_root, expected_entry_name) in cases {
+                let (cl, cr) = path_classify(path, 0);
+
+                assert_eq!(Classification::DeviceRooted, cl, "path='{path}'");
+                assert_eq!(expected_prefix, cr.Prefix.substring_of(path), "path='{path}'");
+                assert_eq!(expected_root, cr.Root.substring_of(path), "path='{path}'");
+                assert_eq!(expected_entry_name, cr.EntryName.substring_of(path), "path='{path}'");
+            }
+        }
+
+        #[test]
+        fn TEST_path_classify_WITH_WslRooted_PATH() {
+            let cases = [
+                (
+                    r"\\wsl.localhost\Ubuntu\home\me\src",
+                    r"\\wsl.localhost\Ubuntu",
+                    "Ubuntu",
+                    "/home/me/src",
+                ),
+                (r"\\wsl$\Debian\etc\hosts", r"\\wsl$\Debian", "Debian", "/etc/hosts"),
+                (
+                    r"\\WSL.LocalHost\Ubuntu-22.04\",
+                    r"\\WSL.LocalHost\Ubuntu-22.04",
+                    "Ubuntu-22.04",
+                    "/",
+                ),
+                (r"\\wsl$\Ubuntu", r"\\wsl$\Ubuntu", "Ubuntu", "/"),
+                (
+                    r"\\?\UNC\wsl.localhost\Ubuntu\tmp",
+                    r"\\?\UNC\wsl.localhost\Ubuntu",
+                    "Ubuntu",
+                    "/tmp",
+                ),
+            ];
+
+            for (path, expected_root, expected_distro, expected_unix_path) in cases {
+                let (cl, cr) = path_classify(path, 0);
+
+                assert_eq!(Classification::WslRooted, cl, "path='{path}'");
+
+                assert_eq!(expected_root, cr.Root.substring_of(path), "path='{path}'");
+                assert_eq!(Some(expected_distro), wsl_distro_name(path, cl, &cr), "path='{path}'");
+
+                #[cfg(feature = "alloc")]
+                {
+                    use crate::libpath::util::windows::wsl_unix_path;
+
+                    assert_eq!(
+                        Some(expected_unix_path),
+                        wsl_unix_path(path, cl, &cr).as_deref(),
+                        "path='{path}'"
+                    );
+                }
+                #[cfg(not(feature = "alloc"))]
+                {
+                    let _ = expected_unix_path;
+                }
+            }
+        }
+
+        #[test]
+        fn TEST_path_classify_WITH_WSL_LIKE_UncRooted_PATH() {
+            for path in [r"\\wsl\Ubuntu\home", r"\\wsl.localhost.example\Ubuntu\home"] {
+                let (cl, cr) = path_classify(path, 0);
+
+                assert_eq!(Classification::UncRooted, cl, "path='{path}'");
+                assert_eq!(None, wsl_distro_name(path, cl, &cr), "path='{path}'");
+            }
+
+            let path = r"\\wsl$";
+            let (cl, _) = path_classify(path, 0);
+
+            assert_eq!(Classification::UncIncomplete, cl);
+        }
+
+        #[test]
+        fn TEST_WindowsSyntax_name_is_reserved() {
+            use crate::libpath::util::{
+                syntax::PathSyntax,
+                windows::WindowsSyntax,
+            };
+
+            for name in [
+                "CON",
+                "con",
+                "Nul.txt",
+                "aux.tar.gz",
+                "COM1",
+                "lpt9.log",
+                "COM\u{B9}",
+                "CONIN$",
+                "NUL .txt",
+                "file.",
+                "file ",
+                "...",
+            ] {
+                assert!(WindowsSyntax::name_is_reserved(name), "name='{name}'");
+            }
+
+            for name in [
+                "CONSOLE",
+                "file.con",
+                "COM",
+                "COM10",
+                "LPT",
+                ".",
+                "..",
+                ".gitignore",
+                "file.ext",
+            ] {
+                assert!(!WindowsSyntax::name_is_reserved(name), "name='{name}'");
+            }
+        }
+
+        #[test]
+        fn TEST_char_upcase() {
+            for (c, expected) in [
+                ('a', 'A'),
+                ('Z', 'Z'),
+                ('1', '1'),
+                ('é', 'É'),
+                // Greek, including final sigma
+                ('α', 'Α'),
+                ('σ', 'Σ'),
+                ('ς', 'Σ'),
+                // Cyrillic
+                ('ж', 'Ж'),
+                ('ё', 'Ё'),
+                // Turkish dotless and dotted i
+                ('ı', 'I'),
+                ('İ', 'İ'),
+                ('i', 'I'),
+                // no single-character upper-case form
+                ('ß', 'ß'),
+                ('ﬀ', 'ﬀ'),
+                // outside of the BMP
+                ('𐐨', '𐐨'),
+            ] {
+                assert_eq!(expected, char_upcase(c), "c='{c}'");
+            }
+        }
+
+        #[test]
+        fn TEST_names_equal_ignore_case() {
+            assert!(names_equal_ignore_case("README.md", "readme.MD"));
+            assert!(names_equal_ignore_case("ΟΔΟΣ", "οδος"));
+            assert!(names_equal_ignore_case("ΟΔΟΣ", "οδοσ"));
+            assert!(names_equal_ignore_case("Москва", "МОСКВА"));
+            assert!(names_equal_ignore_case("dıyarbakır", "DIYARBAKIR"));
+            assert!(names_equal_ignore_case("istanbul", "ISTANBUL"));
+
+            assert!(!names_equal_ignore_case("istanbul", "İSTANBUL"));
+            assert!(!names_equal_ignore_case("straße", "STRASSE"));
+            assert!(!names_equal_ignore_case("file", "files"));
+        }
+
+        #[cfg(feature = "alloc")]
+        #[test]
+        fn TEST_paths_equal_WITH_NON_ASCII_CASE() {
+            use crate::libpath::util::{
+                syntax::comparison_flags::IGNORE_TRAILING_SEPARATOR,
+                windows::{
+                    path_key,
+                    paths_equal,
+                },
+            };
+
+            assert!(paths_equal(r"\\Server\Общий\Файл.txt", "//server/ОБЩИЙ/файл.TXT", 0));
+            assert!(!paths_equal(r"C:\straße", r"C:\STRASSE", 0));
+
+            // the root contains a character whose upper-case form is shorter
+            // in UTF-8, so the trailing separator must be measured in the key
+
+            assert_eq!(
+                r"\\I\SHARE\",
+                path_key(r"\\ı\share\", IGNORE_TRAILING_SEPARATOR).as_str()
+            );
+            assert_eq!(
+                r"\\I\SHARE\",
+                path_key(r"\\ı\share\\", IGNORE_TRAILING_SEPARATOR).as_str()
+            );
+        }
     }
 }
 
@@ -0,0 +1,494 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/archive.rs
+ *
+ * Purpose: Sanitisation of the entry names of archives.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+//! Sanitisation of the entry names of archives, such as zip and tar files,
+//! which may contain roots, drive letters, and `".."` parts with which a
+//! malicious archive could write outside of the extraction directory (the
+//! so-called "zip slip").
+
+use super::{
+    syntax::{
+        PathSyntax,
+        Syntax,
+    },
+    unix::{
+        self,
+        UnixSyntax,
+    },
+    windows::{
+        self,
+        WindowsSyntax,
+    },
+};
+
+use alloc::{
+    borrow::ToOwned,
+    string::String,
+    vec::Vec,
+};
+use core::fmt;
+
+
+/// A change made by [`sanitize_archive_entry()`].
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub enum ArchiveEntryChange {
+    /// A root, such as `"/"`, `"~"`, `"C:"`, or `"\\server\share"`, was
+    /// removed, along with any following separators.
+    RootRemoved(String),
+    /// Backslashes were treated as separators.
+    BackslashesConverted,
+    /// A `"."` part was removed.
+    CurrentDirRemoved,
+    /// A `".."` part was resolved by removing it and the preceding name.
+    ParentDirResolved,
+    /// A `".."` part that would ascend above the extraction directory was
+    /// removed.
+    ParentDirRemoved,
+    /// A character that is invalid in the target syntax was replaced by
+    /// `'_'`.
+    InvalidCharReplaced(char),
+    /// A name that is reserved in the target syntax, such as `"CON"` on
+    /// Windows, was altered by the insertion or substitution of `'_'`.
+    ReservedNameAltered(String),
+}
+
+
+/// The reason that an archive entry name is rejected by
+/// [`sanitize_archive_entry()`].
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub enum ArchiveEntryError {
+    /// The name is a Windows device path, such as `\\.\COM1`.
+    DevicePath,
+    /// The name contains no parts once sanitised, e.g. `"/"` or `"../"`.
+    Empty,
+}
+
+impl fmt::Display for ArchiveEntryError {
+    fn fmt(
+        &self,
+        f : &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::DevicePath => f.write_str("archive entry name is a device path"),
+            Self::Empty => f.write_str("archive entry name is empty once sanitised"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ArchiveEntryError {
+}
+
+
+/// Sanitises the entry name of an archive, so that it may be safely
+/// extracted to a directory.
+///
+/// The name is classified according to both the UNIX and Windows
+/// syntaxes, and any root recognised by either is removed. Both `'/'` and
+/// `'\'` are treated as separators, `"."` and `".."` parts are resolved
+/// (with any that would ascend above the extraction directory removed),
+/// and characters and names that are invalid or reserved in the target
+/// syntax are replaced.
+///
+/// # Parameters:
+/// - `name` - the entry name;
+/// - `target_syntax` - the syntax of the path that is obtained;
+///
+/// # Returns:
+/// `(path : String, changes : Vec<ArchiveEntryChange>)`, where `path` is
+/// a relative path in `target_syntax` that does not ascend above the
+/// extraction directory, and `changes` lists the changes made to `name`
+/// to obtain it, in order.
+///
+/// # Errors:
+/// An [`ArchiveEntryError`] if `name` is a device path, or if nothing
+/// remains of it once sanitised.
+pub fn sanitize_archive_entry(
+    name : &str,
+    target_syntax : Syntax,
+) -> Result<
+    (
+        String,                  // path
+        Vec<ArchiveEntryChange>, // changes
+    ),
+    ArchiveEntryError,
+> {
+    let mut changes = Vec::new();
+
+    // roots, as recognised by either syntax
+
+    let (_, unix_cr) = unix::path_classify(name, unix::classification_flags::IGNORE_INVALID_CHARS);
+    let (windows_cl, windows_cr) = windows::path_classify(name, windows::classification_flags::IGNORE_INVALID_CHARS);
+
+    if windows::Classification::DeviceRooted == windows_cl {
+        return Err(ArchiveEntryError::DevicePath);
+    }
+
+    // the separator of a slash-rooted path is not included in the
+    // classification's Root, so leading separators are removed separately
+
+    let root_len = unix_cr.Root.len().max(windows_cr.Root.len());
+    let root_len = name.len() - name[root_len..].trim_start_matches(['/', '\\']).len();
+
+    if 0 != root_len {
+        changes.push(ArchiveEntryChange::RootRemoved(name[..root_len].to_owned()));
+    }
+
+    let rest = &name[root_len..];
+
+    if rest.contains('\\') {
+        changes.push(ArchiveEntryChange::BackslashesConverted);
+    }
+
+    // dots parts
+
+    let mut parts : Vec<&str> = Vec::new();
+
+    for part in rest.split(['/', '\\']) {
+        match part {
+            "" => (),
+            "." => changes.push(ArchiveEntryChange::CurrentDirRemoved),
+            ".." => {
+                if parts.pop().is_some() {
+                    changes.push(ArchiveEntryChange::ParentDirResolved);
+                } else {
+                    changes.push(ArchiveEntryChange::ParentDirRemoved);
+                }
+            },
+            _ => parts.push(part),
+        }
+    }
+
+    if parts.is_empty() {
+        return Err(ArchiveEntryError::Empty);
+    }
+
+    // invalid characters and reserved names
+
+    let (separator, char_is_invalid, name_is_reserved) : (char, CharPredicate_, NamePredicate_) = match target_syntax {
+        Syntax::Unix => {
+            (
+                UnixSyntax::PREFERRED_SEPARATOR,
+                UnixSyntax::char_is_invalid,
+                UnixSyntax::name_is_reserved,
+            )
+        },
+        Syntax::Windows => {
+            (
+                WindowsSyntax::PREFERRED_SEPARATOR,
+                WindowsSyntax::char_is_invalid,
+                WindowsSyntax::name_is_reserved,
+            )
+        },
+    };
+
+    let mut path = String::with_capacity(rest.len() + 1);
+
+    for part in parts {
+        if !path.is_empty() {
+            path.push(separator);
+        }
+
+        let part_start = path.len();
+
+        for c in part.chars() {
+            if char_is_invalid(c) {
+                changes.push(ArchiveEntryChange::InvalidCharReplaced(c));
+
+                path.push('_');
+            } else {
+                path.push(c);
+            }
+        }
+
+        if name_is_reserved(&path[part_start..]) {
+            let original = path[part_start..].to_owned();
+            let altered = alter_reserved_name_(&original);
+
+            path.truncate(part_start);
+            path.push_str(&altered);
+
+            changes.push(ArchiveEntryChange::ReservedNameAltered(original));
+        }
+    }
+
+    Ok((path, changes))
+}
+
+
+type CharPredicate_ = fn(char) -> bool;
+type NamePredicate_ = fn(&str) -> bool;
+
+/// Alters a reserved name so that it is no longer reserved, by replacing
+/// trailing dots and spaces with `'_'`, and by inserting `'_'` before the
+/// extension of a device name, e.g. `"CON.txt"` => `"CON_.txt"`.
+fn alter_reserved_name_(name : &str) -> String {
+    let trimmed = name.trim_end_matches(['.', ' ']);
+
+    let mut altered = String::with_capacity(name.len() + 1);
+
+    if trimmed.is_empty() {
+        // e.g. "..." or " "
+
+        altered.extend(name.chars().map(|_| '_'));
+
+        return altered;
+    }
+
+    let base_len = trimmed.find('.').unwrap_or(trimmed.len());
+
+    altered.push_str(&trimmed[..base_len]);
+
+    if WindowsSyntax::name_is_reserved(&trimmed[..base_len]) || WindowsSyntax::name_is_reserved(trimmed) {
+        altered.push('_');
+    }
+
+    altered.push_str(&trimmed[base_len..]);
+    altered.extend(name[trimmed.len()..].chars().map(|_| '_'));
+
+    altered
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate::libpath::util::syntax::is_contained_within;
+
+    use alloc::vec;
+
+
+    #[test]
+    fn TEST_sanitize_archive_entry_WITH_SAFE_NAMES() {
+        for (name, target_syntax, expected) in [
+            ("dir/file.ext", Syntax::Unix, "dir/file.ext"),
+            ("dir/file.ext", Syntax::Windows, r"dir\file.ext"),
+            ("file.ext", Syntax::Windows, "file.ext"),
+        ] {
+            assert_eq!(
+                Ok((String::from(expected), vec![])),
+                sanitize_archive_entry(name, target_syntax),
+                "name='{name}'"
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_sanitize_archive_entry_WITH_ROOTS() {
+        use ArchiveEntryChange::*;
+
+        let cases = [
+            ("/etc/passwd", "etc/passwd", vec![RootRemoved("/".into())]),
+            ("///etc/passwd", "etc/passwd", vec![RootRemoved("///".into())]),
+            ("~/.ssh/id_rsa", ".ssh/id_rsa", vec![RootRemoved("~/".into())]),
+            (
+                r"C:\Windows\win.ini",
+                "Windows/win.ini",
+                vec![RootRemoved(r"C:\".into()), BackslashesConverted],
+            ),
+            ("C:file.ext", "file.ext", vec![RootRemoved("C:".into())]),
+            (
+                r"\\server\share\file.ext",
+                "file.ext",
+                vec![RootRemoved(r"\\server\share\".into())],
+            ),
+            (r"\\?\C:\file.ext", "file.ext", vec![RootRemoved(r"\\?\C:\".into())]),
+        ];
+
+        for (name, expected, expected_changes) in cases {
+            assert_eq!(
+                Ok((String::from(expected), expected_changes)),
+                sanitize_archive_entry(name, Syntax::Unix),
+                "name='{name}'"
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_sanitize_archive_entry_WITH_DOTS() {
+        use ArchiveEntryChange::*;
+
+        let cases = [
+            (
+                "../../etc/passwd",
+                "etc/passwd",
+                vec![ParentDirRemoved, ParentDirRemoved],
+            ),
+            (
+                "dir/../../file.ext",
+                "file.ext",
+                vec![ParentDirResolved, ParentDirRemoved],
+            ),
+            (
+                "./dir/./file.ext",
+                "dir/file.ext",
+                vec![CurrentDirRemoved, CurrentDirRemoved],
+            ),
+            (
+                r"dir\..\..\file.ext",
+                "file.ext",
+                vec![BackslashesConverted, ParentDirResolved, ParentDirRemoved],
+            ),
+            ("dir//file.ext", "dir/file.ext", vec![]),
+        ];
+
+        for (name, expected, expected_changes) in cases {
+            assert_eq!(
+                Ok((String::from(expected), expected_changes)),
+                sanitize_archive_entry(name, Syntax::Unix),
+                "name='{name}'"
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_sanitize_archive_entry_WITH_INVALID_CHARS_AND_RESERVED_NAMES() {
+        use ArchiveEntryChange::*;
+
+        let cases = [
+            (
+                "dir/what?.txt",
+                Syntax::Windows,
+                r"dir\what_.txt",
+                vec![InvalidCharReplaced('?')],
+            ),
+            (
+                "ab:c|d",
+                Syntax::Windows,
+                "ab_c_d",
+                vec![InvalidCharReplaced(':'), InvalidCharReplaced('|')],
+            ),
+            ("ab:c|d", Syntax::Unix, "ab:c|d", vec![]),
+            ("fi\0le", Syntax::Unix, "fi_le", vec![InvalidCharReplaced('\0')]),
+            (
+                "dir/CON",
+                Syntax::Windows,
+                r"dir\CON_",
+                vec![ReservedNameAltered("CON".into())],
+            ),
+            (
+                "dir/nul.tar.gz",
+                Syntax::Windows,
+                r"dir\nul_.tar.gz",
+                vec![ReservedNameAltered("nul.tar.gz".into())],
+            ),
+            (
+                "file. ",
+                Syntax::Windows,
+                "file__",
+                vec![ReservedNameAltered("file. ".into())],
+            ),
+            (
+                "dir/.../file",
+                Syntax::Windows,
+                r"dir\___\file",
+                vec![ReservedNameAltered("...".into())],
+            ),
+            ("dir/CON", Syntax::Unix, "dir/CON", vec![]),
+        ];
+
+        for (name, target_syntax, expected, expected_changes) in cases {
+            assert_eq!(
+                Ok((String::from(expected), expected_changes)),
+                sanitize_archive_entry(name, target_syntax),
+                "name='{name}'"
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_sanitize_archive_entry_WITH_REJECTED_NAMES() {
+        let cases = [
+            (r"\\.\COM1", ArchiveEntryError::DevicePath),
+            (r"\\?\Volume{01234567}\file", ArchiveEntryError::DevicePath),
+            ("/", ArchiveEntryError::Empty),
+            ("../", ArchiveEntryError::Empty),
+            ("dir/..", ArchiveEntryError::Empty),
+            ("", ArchiveEntryError::Empty),
+        ];
+
+        for (name, expected) in cases {
+            assert_eq!(
+                Err(expected),
+                sanitize_archive_entry(name, Syntax::Windows),
+                "name='{name}'"
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_sanitize_archive_entry_RESULTS_ARE_CONTAINED() {
+        let names = [
+            "../../etc/passwd",
+            r"..\..\Windows\win.ini",
+            "/abs/../../x",
+            r"C:..\x",
+            r"\\server\share\..\..\x",
+            "dir/./../.../CON/aux.txt/x",
+            "~/../x",
+        ];
+
+        for name in names {
+            let (path, _) = sanitize_archive_entry(name, Syntax::Unix).unwrap();
+
+            assert!(
+                is_contained_within::<UnixSyntax>("/base", &path).is_ok(),
+                "name='{name}', path='{path}'"
+            );
+
+            let (path, _) = sanitize_archive_entry(name, Syntax::Windows).unwrap();
+
+            assert!(
+                is_contained_within::<WindowsSyntax>(r"C:\base", &path).is_ok(),
+                "name='{name}', path='{path}'"
+            );
+        }
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -0,0 +1,637 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/batch.rs
+ *
+ * Purpose: Classification of batches of paths, with aggregate statistics.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+//! Classification of batches of paths - such as the files of a repository
+//! or the entries of a backup manifest - with the aggregation of
+//! statistics in the same pass.
+//!
+//! When the `"rayon"` feature is enabled, [`classify_batch()`] and
+//! [`classify_batch_with_stats()`] classify in parallel, and
+//! `classify_each_par()` is available.
+
+use super::{
+    common::ClassificationResult,
+    syntax::{
+        path_classify,
+        PathSyntax,
+    },
+};
+
+use alloc::{
+    borrow::Cow,
+    collections::BTreeMap,
+    string::String,
+    vec::Vec,
+};
+use core::{
+    fmt,
+    marker::PhantomData,
+};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+
+/// Statistics aggregated over a number of classified paths.
+pub struct BatchStats<S : PathSyntax> {
+    /// The number of paths classified.
+    pub total :       usize,
+    /// The number of paths of each classification.
+    pub counts :      BTreeMap<S::Classification, usize>,
+    /// The number of paths that contain an invalid character, whether or
+    /// not they were classified as invalid (which depends on the flags).
+    pub num_invalid : usize,
+    /// The number of paths with each extension, such as `".rs"`, in which
+    /// the extensions are [case-folded](PathSyntax::char_fold_case), so
+    /// that, for Windows, `".rs"` and `".RS"` are counted together (as
+    /// `".RS"`). Paths without an extension are not counted.
+    pub extensions :  BTreeMap<String, usize>,
+    syntax :          PhantomData<fn() -> S>,
+}
+
+// API functions
+impl<S : PathSyntax> BatchStats<S> {
+    /// Creates an empty instance.
+    pub fn new() -> Self {
+        Self {
+            total :       0,
+            counts :      BTreeMap::new(),
+            num_invalid : 0,
+            extensions :  BTreeMap::new(),
+            syntax :      PhantomData,
+        }
+    }
+}
+
+// Mutating methods
+impl<S : PathSyntax> BatchStats<S> {
+    /// Adds a classified path to the statistics.
+    ///
+    /// # Parameters:
+    /// - `path` - the classified path;
+    /// - `classification` - the classification of `path`;
+    /// - `cr` - the classification result of `path`;
+    pub fn add(
+        &mut self,
+        path : &str,
+        classification : S::Classification,
+        cr : &ClassificationResult,
+    ) {
+        self.total += 1;
+
+        *self.counts.entry(classification).or_default() += 1;
+
+        if !cr.FirstInvalid.is_empty() {
+            self.num_invalid += 1;
+        }
+
+        let extension = cr.Extension.substring_of(path);
+
+        if !extension.is_empty() {
+            // the extension is only copied if it is folded, or is new
+
+            let extension : Cow<str> = if extension.chars().all(|c| S::char_fold_case(c) == c) {
+                Cow::Borrowed(extension)
+            } else {
+                Cow::Owned(extension.chars().map(S::char_fold_case).collect())
+            };
+
+            match self.extensions.get_mut(extension.as_ref()) {
+                Some(count) => *count += 1,
+                None => {
+                    self.extensions.insert(extension.into_owned(), 1);
+                },
+            }
+        }
+    }
+
+    /// Merges the statistics of `other` into this instance.
+    pub fn merge(
+        &mut self,
+        other : Self,
+    ) {
+        self.total += other.total;
+
+        for (classification, count) in other.counts {
+            *self.counts.entry(classification).or_default() += count;
+        }
+
+        self.num_invalid += other.num_invalid;
+
+        for (extension, count) in other.extensions {
+            *self.extensions.entry(extension).or_default() += count;
+        }
+    }
+}
+
+// Non-mutating methods
+impl<S : PathSyntax> BatchStats<S> {
+    /// The number of paths of the given classification.
+    pub fn count_of(
+        &self,
+        classification : S::Classification,
+    ) -> usize {
+        self.counts.get(&classification).copied().unwrap_or(0)
+    }
+}
+
+// Trait implementations
+
+impl<S : PathSyntax> Clone for BatchStats<S> {
+    fn clone(&self) -> Self {
+        Self {
+            total :       self.total,
+            counts :      self.counts.clone(),
+            num_invalid : self.num_invalid,
+            extensions :  self.extensions.clone(),
+            syntax :      PhantomData,
+        }
+    }
+}
+
+impl<S : PathSyntax> fmt::Debug for BatchStats<S> {
+    fn fmt(
+        &self,
+        f : &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("BatchStats")
+            .field("total", &self.total)
+            .field("counts", &self.counts)
+            .field("num_invalid", &self.num_invalid)
+            .field("extensions", &self.extensions)
+            .finish()
+    }
+}
+
+impl<S : PathSyntax> Default for BatchStats<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S : PathSyntax> PartialEq for BatchStats<S> {
+    fn eq(
+        &self,
+        other : &Self,
+    ) -> bool {
+        self.total == other.total
+            && self.counts == other.counts
+            && self.num_invalid == other.num_invalid
+            && self.extensions == other.extensions
+    }
+}
+
+impl<S : PathSyntax> Eq for BatchStats<S> {
+}
+
+
+/// Classifies each of `paths` according to the syntax `S`.
+///
+/// # Parameters:
+/// - `paths` - the paths to be classified;
+/// - `parse_flags` - flags that moderate the classification;
+///
+/// # Returns:
+/// The classification and classification result of each path, in the
+/// same order as `paths`.
+#[cfg(not(feature = "rayon"))]
+pub fn classify_batch<S : PathSyntax>(
+    paths : &[&str],
+    parse_flags : i32,
+) -> Vec<(
+    S::Classification,    // classification
+    ClassificationResult, // classification_result
+)> {
+    paths.iter().map(|path| path_classify::<S>(path, parse_flags)).collect()
+}
+
+/// Classifies each of `paths` according to the syntax `S`, in parallel.
+///
+/// # Parameters:
+/// - `paths` - the paths to be classified;
+/// - `parse_flags` - flags that moderate the classification;
+///
+/// # Returns:
+/// The classification and classification result of each path, in the
+/// same order as `paths`.
+#[cfg(feature = "rayon")]
+pub fn classify_batch<S : PathSyntax>(
+    paths : &[&str],
+    parse_flags : i32,
+) -> Vec<(
+    S::Classification,    // classification
+    ClassificationResult, // classification_result
+)>
+where
+    S::Classification : Send + Sync,
+{
+    paths
+        .par_iter()
+        .map(|path| path_classify::<S>(path, parse_flags))
+        .collect()
+}
+
+/// Classifies each of `paths` according to the syntax `S`, and
+/// aggregates statistics over them in the same pass.
+///
+/// # Parameters:
+/// - `paths` - the paths to be classified;
+/// - `parse_flags` - flags that moderate the classification;
+///
+/// # Returns:
+/// `(results, stats)`, where `results` is as for [`classify_batch()`].
+#[cfg(not(feature = "rayon"))]
+pub fn classify_batch_with_stats<S : PathSyntax>(
+    paths : &[&str],
+    parse_flags : i32,
+) -> (
+    Vec<(S::Classification, ClassificationResult)>, // results
+    BatchStats<S>,                                  // stats
+) {
+    paths
+        .iter()
+        .fold((Vec::with_capacity(paths.len()), BatchStats::new()), |acc, path| {
+            classify_into_(acc, path, parse_flags)
+        })
+}
+
+/// Classifies each of `paths` according to the syntax `S`, in parallel,
+/// and aggregates statistics over them in the same pass.
+///
+/// # Parameters:
+/// - `paths` - the paths to be classified;
+/// - `parse_flags` - flags that moderate the classification;
+///
+/// # Returns:
+/// `(results, stats)`, where `results` is as for [`classify_batch()`].
+#[cfg(feature = "rayon")]
+pub fn classify_batch_with_stats<S : PathSyntax>(
+    paths : &[&str],
+    parse_flags : i32,
+) -> (
+    Vec<(S::Classification, ClassificationResult)>, // results
+    BatchStats<S>,                                  // stats
+)
+where
+    S::Classification : Send + Sync,
+{
+    // each thread accumulates a contiguous run of the results, and the
+    // runs are combined in order
+
+    paths
+        .par_iter()
+        .fold(
+            || (Vec::new(), BatchStats::new()),
+            |acc, path| classify_into_(acc, path, parse_flags),
+        )
+        .reduce(
+            || (Vec::new(), BatchStats::new()),
+            |(mut results, mut stats), (other_results, other_stats)| {
+                results.extend(other_results);
+                stats.merge(other_stats);
+
+                (results, stats)
+            },
+        )
+}
+
+/// Classifies `path`, appending its result to `results` and adding it to
+/// `stats`.
+fn classify_into_<S : PathSyntax>(
+    (mut results, mut stats) : (Vec<(S::Classification, ClassificationResult)>, BatchStats<S>),
+    path : &str,
+    parse_flags : i32,
+) -> (
+    Vec<(S::Classification, ClassificationResult)>, // results
+    BatchStats<S>,                                  // stats
+) {
+    let (cl, cr) = path_classify::<S>(path, parse_flags);
+
+    stats.add(path, cl, &cr);
+    results.push((cl, cr));
+
+    (results, stats)
+}
+
+/// Classifies each of `paths` according to the syntax `S` as they are
+/// obtained, aggregating statistics in the same pass.
+///
+/// # Parameters:
+/// - `paths` - the paths to be classified;
+/// - `parse_flags` - flags that moderate the classification;
+///
+/// # Returns:
+/// An iterator that obtains `(path, classification, classification_result)`
+/// for each path, and whose [`stats()`](ClassifyStream::stats) are those
+/// of the paths obtained so far.
+pub fn classify_stream<S, I>(
+    paths : I,
+    parse_flags : i32,
+) -> ClassifyStream<I::IntoIter, S>
+where
+    S : PathSyntax,
+    I : IntoIterator,
+    I::Item : AsRef<str>,
+{
+    ClassifyStream {
+        paths : paths.into_iter(),
+        parse_flags,
+        stats : BatchStats::new(),
+    }
+}
+
+/// Iterator obtained by [`classify_stream()`].
+pub struct ClassifyStream<I, S : PathSyntax> {
+    paths :       I,
+    parse_flags : i32,
+    stats :       BatchStats<S>,
+}
+
+// Non-mutating methods
+impl<I, S : PathSyntax> ClassifyStream<I, S> {
+    /// The statistics of the paths obtained so far.
+    pub fn stats(&self) -> &BatchStats<S> {
+        &self.stats
+    }
+}
+
+// Mutating methods
+impl<I, S : PathSyntax> ClassifyStream<I, S> {
+    /// Obtains the statistics of the paths obtained so far, consuming the
+    /// instance.
+    pub fn into_stats(self) -> BatchStats<S> {
+        self.stats
+    }
+}
+
+impl<I, S> Iterator for ClassifyStream<I, S>
+where
+    S : PathSyntax,
+    I : Iterator,
+    I::Item : AsRef<str>,
+{
+    type Item = (I::Item, S::Classification, ClassificationResult);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let path = self.paths.next()?;
+
+        let (cl, cr) = path_classify::<S>(path.as_ref(), self.parse_flags);
+
+        self.stats.add(path.as_ref(), cl, &cr);
+
+        Some((path, cl, cr))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.paths.size_hint()
+    }
+}
+
+/// Classifies each of `paths` according to the syntax `S` in parallel,
+/// passing each result to `f` as it is obtained, and aggregating
+/// statistics in the same pass.
+///
+/// This is the parallel equivalent of [`classify_stream()`], for when the
+/// results need not be held, or need not be in order.
+///
+/// # Parameters:
+/// - `paths` - the paths to be classified;
+/// - `parse_flags` - flags that moderate the classification;
+/// - `f` - the function that receives each path, its classification, and
+///   its classification result;
+///
+/// # Returns:
+/// The statistics of all the paths.
+#[cfg(feature = "rayon")]
+pub fn classify_each_par<S, I, F>(
+    paths : I,
+    parse_flags : i32,
+    f : F,
+) -> BatchStats<S>
+where
+    S : PathSyntax,
+    S::Classification : Send + Sync,
+    I : IntoParallelIterator,
+    I::Item : AsRef<str>,
+    F : Fn(I::Item, S::Classification, ClassificationResult) + Sync + Send,
+{
+    paths
+        .into_par_iter()
+        .fold(BatchStats::new, |mut stats, path| {
+            let (cl, cr) = path_classify::<S>(path.as_ref(), parse_flags);
+
+            stats.add(path.as_ref(), cl, &cr);
+
+            f(path, cl, cr);
+
+            stats
+        })
+        .reduce(BatchStats::new, |mut stats, other| {
+            stats.merge(other);
+
+            stats
+        })
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate::libpath::util::{
+        unix::{
+            self,
+            UnixSyntax,
+        },
+        windows::{
+            self,
+            WindowsSyntax,
+        },
+    };
+
+    use alloc::format;
+
+
+    const PATHS : &[&str] = &[
+        "src/lib.rs",
+        "/usr/local/bin/tool",
+        "~/notes.txt",
+        "README.md",
+        "src/main.rs",
+        "bad\0name.rs",
+        "",
+        "/etc/",
+    ];
+
+
+    #[test]
+    fn TEST_classify_batch() {
+        let results = classify_batch::<UnixSyntax>(PATHS, 0);
+
+        assert_eq!(PATHS.len(), results.len());
+
+        for (path, result) in PATHS.iter().zip(results) {
+            assert_eq!(unix::path_classify(path, 0), result, "path='{path}'");
+        }
+    }
+
+    #[test]
+    fn TEST_classify_batch_with_stats() {
+        let (results, stats) = classify_batch_with_stats::<UnixSyntax>(PATHS, 0);
+
+        assert_eq!(classify_batch::<UnixSyntax>(PATHS, 0), results);
+
+        assert_eq!(8, stats.total);
+        assert_eq!(3, stats.count_of(unix::Classification::Relative));
+        assert_eq!(2, stats.count_of(unix::Classification::SlashRooted));
+        assert_eq!(1, stats.count_of(unix::Classification::HomeRooted));
+        assert_eq!(1, stats.count_of(unix::Classification::InvalidChars));
+        assert_eq!(1, stats.count_of(unix::Classification::Empty));
+        assert_eq!(1, stats.num_invalid);
+        assert_eq!(
+            BTreeMap::from([
+                (String::from(".md"), 1),
+                (String::from(".rs"), 3),
+                (String::from(".txt"), 1)
+            ]),
+            stats.extensions
+        );
+    }
+
+    #[test]
+    fn TEST_classify_batch_with_stats_IS_ORDERED_FOR_LARGE_BATCHES() {
+        let paths : Vec<String> = (0..10_000)
+            .map(|i| format!(r"C:\dir-{}\file-{i}.ext{}", i % 7, i % 3))
+            .collect();
+        let paths : Vec<&str> = paths.iter().map(String::as_str).collect();
+
+        let (results, stats) = classify_batch_with_stats::<WindowsSyntax>(&paths, 0);
+
+        for (path, result) in paths.iter().zip(results) {
+            assert_eq!(windows::path_classify(path, 0), result, "path='{path}'");
+        }
+
+        assert_eq!(10_000, stats.total);
+        assert_eq!(10_000, stats.count_of(windows::Classification::DriveLetterRooted));
+        assert_eq!(3, stats.extensions.len());
+        assert_eq!(3334, stats.extensions[".EXT0"]);
+    }
+
+    #[test]
+    fn TEST_BatchStats_add_FOLDS_CASE_OF_EXTENSIONS() {
+        let paths = ["a.rs", "b.RS", "c.Rs", "d.txt"];
+
+        let (_, stats) = classify_batch_with_stats::<WindowsSyntax>(&paths, 0);
+
+        assert_eq!(
+            BTreeMap::from([(String::from(".RS"), 3), (String::from(".TXT"), 1)]),
+            stats.extensions
+        );
+
+        let (_, stats) = classify_batch_with_stats::<UnixSyntax>(&paths, 0);
+
+        assert_eq!(
+            BTreeMap::from([
+                (String::from(".RS"), 1),
+                (String::from(".Rs"), 1),
+                (String::from(".rs"), 1),
+                (String::from(".txt"), 1)
+            ]),
+            stats.extensions
+        );
+    }
+
+    #[test]
+    fn TEST_classify_stream() {
+        let mut stream = classify_stream::<UnixSyntax, _>(PATHS.iter().map(|&path| String::from(path)), 0);
+
+        let (path, cl, _) = stream.next().unwrap();
+
+        assert_eq!("src/lib.rs", path);
+        assert_eq!(unix::Classification::Relative, cl);
+        assert_eq!(1, stream.stats().total);
+
+        assert_eq!(PATHS.len() - 1, stream.by_ref().count());
+
+        let (_, expected) = classify_batch_with_stats::<UnixSyntax>(PATHS, 0);
+
+        assert_eq!(expected, stream.into_stats());
+    }
+
+    #[test]
+    fn TEST_BatchStats_merge() {
+        let (_, mut stats) = classify_batch_with_stats::<UnixSyntax>(&PATHS[..3], 0);
+        let (_, other) = classify_batch_with_stats::<UnixSyntax>(&PATHS[3..], 0);
+
+        stats.merge(other);
+
+        let (_, expected) = classify_batch_with_stats::<UnixSyntax>(PATHS, 0);
+
+        assert_eq!(expected, stats);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn TEST_classify_each_par() {
+        use std::sync::Mutex;
+
+        let seen = Mutex::new(Vec::new());
+
+        let stats = classify_each_par::<UnixSyntax, _, _>(PATHS.par_iter().copied(), 0, |path, cl, _| {
+            seen.lock().unwrap().push((path, cl));
+        });
+
+        let (_, expected) = classify_batch_with_stats::<UnixSyntax>(PATHS, 0);
+
+        assert_eq!(expected, stats);
+
+        let mut seen = seen.into_inner().unwrap();
+
+        seen.sort();
+
+        assert_eq!(PATHS.len(), seen.len());
+        assert!(seen.contains(&("README.md", unix::Classification::Relative)));
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -0,0 +1,316 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/collisions.rs
+ *
+ * Purpose: Detection of paths that collide on case-insensitive, or
+ *          normalising, file-systems.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+//! Detection of paths that collide on file-systems that are
+//! case-insensitive and/or that normalise Unicode names, such as
+//! `"README.md"` and `"readme.md"`.
+
+use super::{
+    common::{
+        Component,
+        Components,
+    },
+    syntax::{
+        classification_flags,
+        path_classify,
+        PathSyntax,
+    },
+};
+
+use caseless::Caseless;
+use unicode_normalization::UnicodeNormalization;
+
+use alloc::{
+    collections::BTreeMap,
+    string::String,
+    vec::Vec,
+};
+
+
+/// The kind of a [`CollisionGroup`].
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub enum CollisionKind {
+    /// The names differ only in case, e.g. `"README.md"` and
+    /// `"readme.md"`.
+    Case,
+    /// The names differ only in Unicode normalisation, e.g. `"café"` in
+    /// NFC and in NFD.
+    Normalisation,
+    /// The names differ both in case and in Unicode normalisation.
+    CaseAndNormalisation,
+}
+
+
+/// A group of entries, in the same directory, whose names collide.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub struct CollisionGroup<'a> {
+    /// The directory of the entries, including any trailing separator, as
+    /// it appears in the first of [`paths`](Self::paths). This is `""`
+    /// for entries in the current directory.
+    pub directory : &'a str,
+    /// The kind of collision.
+    pub kind :      CollisionKind,
+    /// The colliding entries, each a prefix of one of the given paths, in
+    /// the order in which they were first encountered.
+    pub paths :     Vec<&'a str>,
+}
+
+
+/// Finds the entries of `paths` that collide on a file-system that is
+/// case-insensitive and/or that normalises Unicode names.
+///
+/// Each path is classified according to the syntax `S`, and each of its
+/// names (including those of its directories) is compared with those of
+/// the same directory in the other paths using full Unicode case folding
+/// and canonical equivalence. A collision between directories is reported
+/// once, for the directories themselves, and not again for the entries
+/// within them.
+///
+/// # Parameters:
+/// - `paths` - the paths to be examined;
+///
+/// # Returns:
+/// The groups of colliding entries, ordered by directory and name.
+///
+/// # Note:
+/// `"."` parts are ignored and `".."` parts are resolved lexically, without
+/// reference to the file-system.
+pub fn find_collisions<'a, S, I>(paths : I) -> Vec<CollisionGroup<'a>>
+where
+    S : PathSyntax,
+    I : IntoIterator<Item = &'a str>,
+{
+    // folded key => (directory, [(name, prefix)])
+    let mut entries = BTreeMap::new();
+
+    for path in paths {
+        let (cl, cr) = path_classify::<S>(path, classification_flags::IGNORE_INVALID_CHARS);
+
+        let mut keys : Vec<String> = Vec::new();
+        let mut root_key = String::new();
+
+        for component in Components::new(path, &cr, S::is_home_rooted(cl), S::char_is_path_name_separator) {
+            match component {
+                Component::Prefix(prefix) | Component::Home(prefix) => {
+                    root_key.extend(
+                        prefix
+                            .chars()
+                            .map(|c| if S::char_is_path_name_separator(c) { '/' } else { c }),
+                    );
+                    root_key = fold_(&root_key);
+                },
+                Component::RootDir => root_key.push('/'),
+                Component::CurDir => (),
+                Component::ParentDir => {
+                    keys.pop();
+                },
+                Component::Normal(name) => {
+                    let offset = name.as_ptr() as usize - path.as_ptr() as usize;
+                    let directory = &path[..offset];
+                    let prefix = &path[..offset + name.len()];
+
+                    let mut key = root_key.clone();
+
+                    for k in &keys {
+                        key.push_str(k);
+                        key.push('/');
+                    }
+
+                    let name_key = fold_(name);
+
+                    key.push_str(&name_key);
+                    keys.push(name_key);
+
+                    let (_, names) = entries.entry(key).or_insert_with(|| (directory, Vec::new()));
+
+                    if !names.iter().any(|&(n, _)| n == name) {
+                        names.push((name, prefix));
+                    }
+                },
+            }
+        }
+    }
+
+    entries
+        .into_values()
+        .filter(|(_, names)| names.len() > 1)
+        .map(|(directory, names)| {
+            CollisionGroup {
+                directory,
+                kind : collision_kind_(names.iter().map(|&(name, _)| name)),
+                paths : names.into_iter().map(|(_, prefix)| prefix).collect(),
+            }
+        })
+        .collect()
+}
+
+
+/// Obtains the key under which names that are canonical caseless matches
+/// are equal, i.e. `NFD(CaseFold(NFD(name)))`.
+fn fold_(name : &str) -> String {
+    name.chars().nfd().default_case_fold().nfd().collect()
+}
+
+/// Determines the kind of collision between `names`, which are known to
+/// collide.
+fn collision_kind_<'a>(mut names : impl Iterator<Item = &'a str> + Clone) -> CollisionKind {
+    let first = names.next().unwrap_or_default();
+
+    let nfc_first : String = first.nfc().collect();
+
+    if names.clone().all(|name| name.nfc().eq(nfc_first.chars())) {
+        return CollisionKind::Normalisation;
+    }
+
+    let folded_first : String = first.chars().default_case_fold().collect();
+
+    if names.all(|name| name.chars().default_case_fold().eq(folded_first.chars())) {
+        CollisionKind::Case
+    } else {
+        CollisionKind::CaseAndNormalisation
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate::libpath::util::{
+        unix::UnixSyntax,
+        windows::WindowsSyntax,
+    };
+
+
+    #[test]
+    fn TEST_find_collisions_WITH_NO_COLLISIONS() {
+        let paths = ["README.md", "src/lib.rs", "src/main.rs", "src/lib.rs", "./src/lib.rs"];
+
+        assert!(find_collisions::<WindowsSyntax, _>(paths).is_empty());
+    }
+
+    #[test]
+    fn TEST_find_collisions_WITH_CASE_COLLISIONS() {
+        let paths = ["README.md", "docs/a.txt", "readme.md", "docs/A.TXT", "docs/b.txt"];
+
+        let groups = find_collisions::<WindowsSyntax, _>(paths);
+
+        assert_eq!(
+            vec![
+                CollisionGroup {
+                    directory : "docs/",
+                    kind :      CollisionKind::Case,
+                    paths :     vec!["docs/a.txt", "docs/A.TXT"],
+                },
+                CollisionGroup {
+                    directory : "",
+                    kind :      CollisionKind::Case,
+                    paths :     vec!["README.md", "readme.md"],
+                },
+            ],
+            groups
+        );
+    }
+
+    #[test]
+    fn TEST_find_collisions_WITH_FULL_CASE_FOLDING() {
+        // "ß" folds to "ss", and "Σ"/"ς"/"σ" all fold to "σ"
+
+        let paths = ["straße", "STRASSE", "ΟΔΟΣ", "οδος"];
+
+        let groups = find_collisions::<UnixSyntax, _>(paths);
+
+        assert_eq!(2, groups.len());
+        assert_eq!(vec!["straße", "STRASSE"], groups[0].paths);
+        assert_eq!(vec!["ΟΔΟΣ", "οδος"], groups[1].paths);
+    }
+
+    #[test]
+    fn TEST_find_collisions_WITH_NORMALISATION_COLLISIONS() {
+        let nfc = "dir/caf\u{e9}";
+        let nfd = "dir/cafe\u{301}";
+        let nfd_upper = "dir/CAFE\u{301}";
+
+        let groups = find_collisions::<UnixSyntax, _>([nfc, nfd]);
+
+        assert_eq!(1, groups.len());
+        assert_eq!(CollisionKind::Normalisation, groups[0].kind);
+        assert_eq!(vec![nfc, nfd], groups[0].paths);
+
+        let groups = find_collisions::<UnixSyntax, _>([nfc, nfd_upper]);
+
+        assert_eq!(1, groups.len());
+        assert_eq!(CollisionKind::CaseAndNormalisation, groups[0].kind);
+    }
+
+    #[test]
+    fn TEST_find_collisions_WITH_DIRECTORY_COLLISIONS() {
+        let paths = [r"Src\lib.rs", "src/main.rs", "src/LIB.rs"];
+
+        let groups = find_collisions::<WindowsSyntax, _>(paths);
+
+        assert_eq!(2, groups.len());
+        assert_eq!("", groups[0].directory);
+        assert_eq!(vec!["Src", "src"], groups[0].paths);
+        assert_eq!(r"Src\", groups[1].directory);
+        assert_eq!(vec![r"Src\lib.rs", "src/LIB.rs"], groups[1].paths);
+    }
+
+    #[test]
+    fn TEST_find_collisions_WITH_ROOTS() {
+        let paths = [r"C:\dir\file", r"c:\DIR\file", r"D:\dir\FILE"];
+
+        let groups = find_collisions::<WindowsSyntax, _>(paths);
+
+        assert_eq!(1, groups.len());
+        assert_eq!(r"C:\", groups[0].directory);
+        assert_eq!(vec![r"C:\dir", r"c:\DIR"], groups[0].paths);
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
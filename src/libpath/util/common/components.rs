@@ -0,0 +1,316 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/common/components.rs
+ *
+ * Purpose: Path components, obtained from a classified path, and their
+ *          conversion to std::path::Component.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+use super::ClassificationResult;
+
+#[cfg(feature = "std")]
+use std::{
+    ffi::OsStr,
+    path::{
+        self as std_path,
+        Path,
+    },
+};
+
+
+/// A single component of a classified path.
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub enum Component<'a> {
+    /// A root that is not itself a path-name separator, such as `"C:"` in
+    /// a Windows path.
+    Prefix(&'a str),
+    /// The home designator, `"~"`.
+    Home(&'a str),
+    /// The root directory, i.e. a separator that immediately follows the
+    /// prefix (if any).
+    RootDir,
+    /// The current directory, `"."`.
+    CurDir,
+    /// The parent directory, `".."`.
+    ParentDir,
+    /// A named directory or entry.
+    Normal(&'a str),
+}
+
+#[cfg(feature = "std")]
+impl<'a> From<Component<'a>> for std_path::Component<'a> {
+    /// Maps a libpath component to its `std::path` equivalent.
+    ///
+    /// # Note:
+    /// `std::path::PrefixComponent` cannot be constructed directly, so a
+    /// [`Component::Prefix`] is mapped to whatever the host platform makes
+    /// of it: on Windows `"C:"` becomes a `Prefix`, whereas elsewhere it
+    /// becomes `Normal("C:")`. A prefix that the host platform sees as
+    /// several components, such as the foreign drive root `"/mnt/c"`, is
+    /// mapped, whole, to `Normal`; use [`Components::into_std()`] to obtain
+    /// those components. The home designator always maps to `Normal("~")`,
+    /// just as it does in `std::path`.
+    fn from(component : Component<'a>) -> Self {
+        match component {
+            Component::Prefix(s) => {
+                let mut components = Path::new(s).components();
+
+                match (components.next(), components.next()) {
+                    (Some(component), None) => component,
+                    _ => std_path::Component::Normal(OsStr::new(s)),
+                }
+            },
+            Component::Home(s) => std_path::Component::Normal(OsStr::new(s)),
+            Component::RootDir => std_path::Component::RootDir,
+            Component::CurDir => std_path::Component::CurDir,
+            Component::ParentDir => std_path::Component::ParentDir,
+            Component::Normal(s) => std_path::Component::Normal(OsStr::new(s)),
+        }
+    }
+}
+
+
+/// Iterator over the [`Component`]s of a classified path.
+///
+/// Runs of separators are treated as a single separator.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct Components<'a> {
+    prefix :       Option<Component<'a>>,
+    has_root_dir : bool,
+    rest :         &'a str,
+    is_separator : fn(char) -> bool,
+}
+
+impl<'a> Components<'a> {
+    /// Creates an instance from a path and its classification result.
+    ///
+    /// # Parameters:
+    /// - `path` - the classified path;
+    /// - `cr` - the classification result obtained for `path`;
+    /// - `is_home` - whether the root of `path` is the home designator;
+    /// - `is_separator` - the path-name separator predicate for the syntax;
+    #[cfg_attr(not(feature = "alloc"), allow(dead_code))]
+    pub(crate) fn new(
+        path : &'a str,
+        cr : &ClassificationResult,
+        is_home : bool,
+        is_separator : fn(char) -> bool,
+    ) -> Self {
+        let root = cr.Root.substring_of(path);
+        let mut rest = &path[cr.Root.offset + cr.Root.len()..cr.Input.offset + cr.Input.len()];
+
+        let prefix = if root.is_empty() {
+            None
+        } else if is_home {
+            Some(Component::Home(root))
+        } else {
+            Some(Component::Prefix(root))
+        };
+
+        let mut has_root_dir = false;
+
+        if !is_home {
+            if let Some(c) = rest.chars().next() {
+                if is_separator(c) {
+                    has_root_dir = true;
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+
+        Self {
+            prefix,
+            has_root_dir,
+            rest,
+            is_separator,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> Components<'a> {
+    /// Converts into an iterator over the `std::path` equivalents of the
+    /// components, which may be collected into a `PathBuf` that has the
+    /// same components as the path.
+    ///
+    /// Unlike the conversion of each component by `From`, a
+    /// [`Component::Prefix`] that the host platform sees as several
+    /// components, such as the foreign drive root `"/mnt/c"`, is expanded
+    /// into those components, e.g. `RootDir`, `Normal("mnt")`, and
+    /// `Normal("c")`.
+    pub fn into_std(self) -> StdComponents<'a> {
+        StdComponents {
+            components :    self,
+            prefix :        None,
+            skip_root_dir : false,
+        }
+    }
+}
+
+impl<'a> Iterator for Components<'a> {
+    type Item = Component<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(prefix) = self.prefix.take() {
+            return Some(prefix);
+        }
+
+        if self.has_root_dir {
+            self.has_root_dir = false;
+
+            return Some(Component::RootDir);
+        }
+
+        while !self.rest.is_empty() {
+            let (part, rest) = match self.rest.find(self.is_separator) {
+                Some(index) => {
+                    let sep_len = self.rest[index..].chars().next().map_or(1, char::len_utf8);
+
+                    (&self.rest[..index], &self.rest[index + sep_len..])
+                },
+                None => (self.rest, ""),
+            };
+
+            self.rest = rest;
+
+            match part {
+                "" => continue,
+                "." => return Some(Component::CurDir),
+                ".." => return Some(Component::ParentDir),
+                _ => return Some(Component::Normal(part)),
+            }
+        }
+
+        None
+    }
+}
+
+
+/// Iterator over the `std::path::Component`s of a classified path, as
+/// obtained from [`Components::into_std()`].
+#[cfg(feature = "std")]
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct StdComponents<'a> {
+    components :    Components<'a>,
+    prefix :        Option<std_path::Components<'a>>,
+    skip_root_dir : bool,
+}
+
+#[cfg(feature = "std")]
+impl<'a> Iterator for StdComponents<'a> {
+    type Item = std_path::Component<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(prefix) = &mut self.prefix {
+                match prefix.next() {
+                    Some(component) => {
+                        // a prefix that is not a `std::path` prefix, such as
+                        // "/mnt/c" or (other than on Windows) "C:", is not
+                        // followed by a root directory, since pushing one
+                        // would discard what precedes it
+
+                        self.skip_root_dir = !matches!(component, std_path::Component::Prefix(_));
+
+                        return Some(component);
+                    },
+                    None => self.prefix = None,
+                }
+            }
+
+            match self.components.next()? {
+                Component::Prefix(s) => self.prefix = Some(Path::new(s).components()),
+                Component::RootDir if self.skip_root_dir => (),
+                component => return Some(component.into()),
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    use std::path::Component as StdComponent;
+
+
+    #[test]
+    fn TEST_Component_INTO_std_Component() {
+        assert_eq!(StdComponent::RootDir, StdComponent::from(Component::RootDir));
+        assert_eq!(StdComponent::CurDir, StdComponent::from(Component::CurDir));
+        assert_eq!(StdComponent::ParentDir, StdComponent::from(Component::ParentDir));
+        assert_eq!(
+            StdComponent::Normal(OsStr::new("abc")),
+            StdComponent::from(Component::Normal("abc"))
+        );
+        assert_eq!(
+            StdComponent::Normal(OsStr::new("~")),
+            StdComponent::from(Component::Home("~"))
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn TEST_Component_Prefix_INTO_std_Component_ON_NON_WINDOWS() {
+        assert_eq!(
+            StdComponent::Normal(OsStr::new("C:")),
+            StdComponent::from(Component::Prefix("C:"))
+        );
+    }
+
+    #[test]
+    fn TEST_Component_Prefix_OF_SEVERAL_PARTS_INTO_std_Component() {
+        for prefix in ["/c", "/mnt/c", "/cygdrive/c"] {
+            assert_eq!(
+                StdComponent::Normal(OsStr::new(prefix)),
+                StdComponent::from(Component::Prefix(prefix)),
+                "prefix='{prefix}'"
+            );
+        }
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -0,0 +1,207 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/common/expanded.rs
+ *
+ * Purpose: Definition of ExpandedClassificationResult, an alternative
+ *          representation of ClassificationResult that contains the text of each
+ *          element rather than its position.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+#![allow(non_snake_case)]
+
+use super::{
+    ClassificationResult,
+    PositionalSlice as PoSl,
+};
+
+use alloc::borrow::Cow;
+
+
+/// Alternative representation of a [`ClassificationResult`] in which each
+/// element is given as the text it spans in the classified path, rather
+/// than as an offset and length.
+///
+/// This is useful for logging and for transmitting a classification to
+/// parties that do not have (or do not want to slice) the original path.
+///
+/// # Note:
+/// The elements are `Cow`s so that an instance may be deserialised from
+/// text that requires unescaping (such as a Windows path in JSON).
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExpandedClassificationResult<'a> {
+    /// The input string.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub Input :                 Cow<'a, str>,
+    /// The full path.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub FullPath :              Cow<'a, str>,
+    /// The prefix.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub Prefix :                Cow<'a, str>,
+    /// The location, i.e. the root and the directory.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub Location :              Cow<'a, str>,
+    /// The root.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub Root :                  Cow<'a, str>,
+    /// The directory.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub Directory :             Cow<'a, str>,
+    /// The number of directory parts.
+    pub NumDirectoryParts :     usize,
+    /// The number of directory parts that are dots directories.
+    pub NumDotsDirectoryParts : usize,
+    /// The entry name.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub EntryName :             Cow<'a, str>,
+    /// The entry name's stem.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub Stem :                  Cow<'a, str>,
+    /// The entry name's extension.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub Extension :             Cow<'a, str>,
+    /// The first invalid character, if any.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub FirstInvalid :          Cow<'a, str>,
+}
+
+// API functions
+impl<'a> ExpandedClassificationResult<'a> {
+    /// Creates an instance from `path` and its classification result.
+    ///
+    /// # Parameters:
+    /// - `path` - the classified path;
+    /// - `cr` - the classification result obtained for `path`;
+    ///
+    /// # Preconditions:
+    /// * `cr` was obtained by classifying `path` - will panic if any of its
+    ///   elements are outside of `path` or not on a `char` boundary;
+    pub fn new(
+        path : &'a str,
+        cr : &ClassificationResult,
+    ) -> Self {
+        let s = |ps : PoSl| Cow::Borrowed(ps.substring_of(path));
+
+        Self {
+            Input :                 s(cr.Input),
+            FullPath :              s(cr.FullPath),
+            Prefix :                s(cr.Prefix),
+            Location :              s(cr.Location),
+            Root :                  s(cr.Root),
+            Directory :             s(cr.Directory),
+            NumDirectoryParts :     cr.NumDirectoryParts,
+            NumDotsDirectoryParts : cr.NumDotsDirectoryParts,
+            EntryName :             s(cr.EntryName),
+            Stem :                  s(cr.Stem),
+            Extension :             s(cr.Extension),
+            FirstInvalid :          s(cr.FirstInvalid),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate::libpath::util::unix::path_classify;
+
+
+    #[test]
+    fn TEST_ExpandedClassificationResult_new() {
+        let path = "~/dir/sub-dir/file.ext";
+        let (_, cr) = path_classify(path, 0);
+
+        let ecr = ExpandedClassificationResult::new(path, &cr);
+
+        assert_eq!("~/dir/sub-dir/file.ext", ecr.Input);
+        assert_eq!("", ecr.Prefix);
+        assert_eq!("~/dir/sub-dir/", ecr.Location);
+        assert_eq!("~", ecr.Root);
+        assert_eq!("/dir/sub-dir/", ecr.Directory);
+        assert_eq!(3, ecr.NumDirectoryParts);
+        assert_eq!(0, ecr.NumDotsDirectoryParts);
+        assert_eq!("file.ext", ecr.EntryName);
+        assert_eq!("file", ecr.Stem);
+        assert_eq!(".ext", ecr.Extension);
+        assert_eq!("", ecr.FirstInvalid);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn TEST_ExpandedClassificationResult_serde_ROUNDTRIP_WITH_ESCAPES() {
+        use crate::libpath::util::windows;
+
+        let path = r"C:\dir\file.ext";
+        let (_, cr) = windows::path_classify(path, 0);
+
+        let ecr = ExpandedClassificationResult::new(path, &cr);
+
+        let json = serde_json::to_string(&ecr).unwrap();
+
+        assert!(json.contains(r#""Directory":"\\dir\\""#), "json={json}");
+
+        let ecr2 : ExpandedClassificationResult = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(ecr, ecr2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn TEST_ExpandedClassificationResult_serde_ROUNDTRIP() {
+        let path = "dir/file.ext";
+        let (_, cr) = path_classify(path, 0);
+
+        let ecr = ExpandedClassificationResult::new(path, &cr);
+
+        let json = serde_json::to_string(&ecr).unwrap();
+
+        assert!(json.contains(r#""EntryName":"file.ext""#), "json={json}");
+        assert!(json.contains(r#""NumDirectoryParts":1"#), "json={json}");
+
+        let ecr2 : ExpandedClassificationResult = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(ecr, ecr2);
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
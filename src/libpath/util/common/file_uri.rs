@@ -0,0 +1,225 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/common/file_uri.rs
+ *
+ * Purpose: Definition of FileUriError, and facilities common to the file URI
+ *          conversions of the unix and windows modules.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+use alloc::{
+    string::String,
+    vec::Vec,
+};
+use core::fmt;
+
+
+/// An error in converting a path to, or from, a `file:` URI.
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub enum FileUriError {
+    /// The URI does not have the `file:` scheme.
+    NotFileUri,
+    /// The URI contains a `'%'` that is not followed by two hexadecimal
+    /// digits.
+    InvalidPercentEncoding,
+    /// The decoded URI is not valid UTF-8.
+    NotUnicode,
+    /// The URI names a host that cannot be represented in the path syntax,
+    /// such as any host other than `localhost` for a UNIX path.
+    UnsupportedHost,
+    /// The path is not absolute - i.e. rooted at `/` for a UNIX path, or at
+    /// a drive or UNC share for a Windows path - and so has no URI.
+    NotAbsolute,
+    /// The path contains characters that are invalid in its syntax.
+    InvalidChars,
+}
+
+impl fmt::Display for FileUriError {
+    fn fmt(
+        &self,
+        f : &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::NotFileUri => f.write_str("URI is not a file URI"),
+            Self::InvalidPercentEncoding => f.write_str("URI contains an invalid percent-encoding"),
+            Self::NotUnicode => f.write_str("URI does not decode to valid Unicode"),
+            Self::UnsupportedHost => f.write_str("URI host cannot be represented in a path"),
+            Self::NotAbsolute => f.write_str("path is not absolute"),
+            Self::InvalidChars => f.write_str("path contains invalid characters"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FileUriError {
+}
+
+
+/// Appends `s` to `uri`, percent-encoding every byte other than those of
+/// the unreserved characters and `'/'`.
+pub(crate) fn percent_encode_into_(
+    uri : &mut String,
+    s : &str,
+) {
+    const HEX_DIGITS : &[u8; 16] = b"0123456789ABCDEF";
+
+    for &b in s.as_bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~' | b'/') {
+            uri.push(b as char);
+        } else {
+            uri.push('%');
+            uri.push(HEX_DIGITS[usize::from(b >> 4)] as char);
+            uri.push(HEX_DIGITS[usize::from(b & 0x0F)] as char);
+        }
+    }
+}
+
+/// Decodes the percent-encoded string `s`.
+pub(crate) fn percent_decode_(s : &str) -> Result<String, FileUriError> {
+    let bytes = s.as_bytes();
+
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut ix = 0;
+
+    while ix < bytes.len() {
+        if b'%' == bytes[ix] {
+            let hex = |b : u8| (b as char).to_digit(16);
+
+            match (
+                bytes.get(ix + 1).and_then(|&b| hex(b)),
+                bytes.get(ix + 2).and_then(|&b| hex(b)),
+            ) {
+                (Some(hi), Some(lo)) => decoded.push((hi * 16 + lo) as u8),
+                _ => return Err(FileUriError::InvalidPercentEncoding),
+            }
+
+            ix += 3;
+        } else {
+            decoded.push(bytes[ix]);
+
+            ix += 1;
+        }
+    }
+
+    String::from_utf8(decoded).map_err(|_| FileUriError::NotUnicode)
+}
+
+/// Splits a `file:` URI into its (still encoded) authority, if it has
+/// one, and its path, discarding any query and fragment.
+pub(crate) fn split_file_uri_(uri : &str) -> Result<(Option<&str>, &str), FileUriError> {
+    const SCHEME : &str = "file:";
+
+    match uri.get(..SCHEME.len()) {
+        Some(scheme) if scheme.eq_ignore_ascii_case(SCHEME) => (),
+        _ => return Err(FileUriError::NotFileUri),
+    }
+
+    let rest = &uri[SCHEME.len()..];
+    let rest = &rest[..rest.find(['?', '#']).unwrap_or(rest.len())];
+
+    match rest.strip_prefix("//") {
+        Some(authority_and_path) => {
+            let authority_len = authority_and_path.find('/').unwrap_or(authority_and_path.len());
+
+            Ok((
+                Some(&authority_and_path[..authority_len]),
+                &authority_and_path[authority_len..],
+            ))
+        },
+        None => Ok((None, rest)),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+
+    #[test]
+    fn TEST_percent_encode_into_() {
+        let mut uri = String::new();
+
+        percent_encode_into_(&mut uri, "/dir name/file#1%.ext");
+
+        assert_eq!("/dir%20name/file%231%25.ext", uri);
+
+        let mut uri = String::new();
+
+        percent_encode_into_(&mut uri, "/caf\u{E9}");
+
+        assert_eq!("/caf%C3%A9", uri);
+    }
+
+    #[test]
+    fn TEST_percent_decode_() {
+        assert_eq!(
+            Ok(String::from("/dir name/file#1%.ext")),
+            percent_decode_("/dir%20name/file%231%25.ext")
+        );
+        assert_eq!(Ok(String::from("/caf\u{E9}")), percent_decode_("/caf%c3%a9"));
+        assert_eq!(Err(FileUriError::InvalidPercentEncoding), percent_decode_("/a%2"));
+        assert_eq!(Err(FileUriError::InvalidPercentEncoding), percent_decode_("/a%zz"));
+        assert_eq!(Err(FileUriError::NotUnicode), percent_decode_("/a%FF"));
+    }
+
+    #[test]
+    fn TEST_split_file_uri_() {
+        assert_eq!(Ok((Some(""), "/dir/file")), split_file_uri_("file:///dir/file"));
+        assert_eq!(
+            Ok((Some("localhost"), "/dir/file")),
+            split_file_uri_("FILE://localhost/dir/file")
+        );
+        assert_eq!(
+            Ok((Some("server"), "/share/file")),
+            split_file_uri_("file://server/share/file")
+        );
+        assert_eq!(Ok((Some("server"), "")), split_file_uri_("file://server"));
+        assert_eq!(
+            Ok((None, "/dir/file")),
+            split_file_uri_("file:/dir/file?query#fragment")
+        );
+        assert_eq!(Err(FileUriError::NotFileUri), split_file_uri_("http://server/dir/file"));
+        assert_eq!(Err(FileUriError::NotFileUri), split_file_uri_("file"));
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
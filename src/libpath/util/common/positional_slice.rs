@@ -0,0 +1,226 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/common/positional_slice.rs
+ *
+ * Purpose: Definition of PositionalSlice.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+
+/// A slice representation of offset and length.
+///
+/// # Note:
+/// This has the same definition as the type of the same name in
+/// **FastParse.Rust**, which is not `no_std`.
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PositionalSlice {
+    /// The slice offset in the source sequence.
+    pub offset : usize,
+    /// The length of the slice.
+    pub length : usize,
+}
+
+// API functions
+impl PositionalSlice {
+    /// Creates an empty instance.
+    pub fn empty() -> Self {
+        Self::new(0, 0)
+    }
+
+    /// Creates an instance with the given `off`set and `len`gth.
+    ///
+    /// # Parameters:
+    /// - `off` - The offset of the slice;
+    /// - `len` - The length of the slice;
+    pub fn new(
+        off : usize,
+        len : usize,
+    ) -> Self {
+        Self {
+            offset : off,
+            length : len,
+        }
+    }
+}
+
+// Non-mutating methods
+impl PositionalSlice {
+    /// Indicates whether the slice is empty.
+    pub fn is_empty(&self) -> bool {
+        0 == self.length
+    }
+
+    /// Indicates the length of the slice.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Obtains unchecked a copy of the slice moved by the given `d`elta.
+    ///
+    /// # Parameters:
+    /// - `d` - The delta;
+    ///
+    /// # Preconditions:
+    /// * `-d <= self.offset` - will panic (in debug) if false;
+    pub fn offset_unchecked(
+        &self,
+        d : isize,
+    ) -> Self {
+        let offset = if d < 0 {
+            self.offset - d.unsigned_abs()
+        } else {
+            self.offset + d as usize
+        };
+
+        Self::new(offset, self.length)
+    }
+
+    /// Obtains checked a copy of the slice moved by the given `d`elta.
+    ///
+    /// # Parameters:
+    /// - `d` - The delta;
+    ///
+    /// # Returns:
+    /// `None` if the offset would be moved below 0, or the end of the
+    /// slice above `usize::MAX`.
+    pub fn offset_checked(
+        &self,
+        d : isize,
+    ) -> Option<Self> {
+        let offset = self.offset.checked_add_signed(d)?;
+
+        offset.checked_add(self.length)?;
+
+        Some(Self::new(offset, self.length))
+    }
+
+    /// Applies this positional slice to a slice of arbitrary type,
+    /// obtaining a relative slice as a result.
+    ///
+    /// # Preconditions:
+    /// * the slice lies within `slice` - will panic if false;
+    pub fn subslice_of<'a, T>(
+        &self,
+        slice : &'a [T],
+    ) -> &'a [T] {
+        &slice[self.offset..self.offset + self.length]
+    }
+
+    /// Applies this positional slice to a string slice, obtaining a
+    /// relative slice as a result.
+    ///
+    /// # Preconditions:
+    /// * the slice lies within `slice`, and on `char` boundaries - will
+    ///   panic if false;
+    pub fn substring_of<'a>(
+        &self,
+        slice : &'a str,
+    ) -> &'a str {
+        &slice[self.offset..self.offset + self.length]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+
+    #[test]
+    fn TEST_PositionalSlice_empty_AND_new() {
+        assert!(PositionalSlice::empty().is_empty());
+        assert_eq!(PositionalSlice::empty(), PositionalSlice::new(0, 0));
+        assert!(PositionalSlice::new(1, 0).is_empty());
+        assert!(!PositionalSlice::new(0, 1).is_empty());
+        assert_eq!(3, PositionalSlice::new(2, 3).len());
+    }
+
+    #[test]
+    fn TEST_PositionalSlice_ORDERING() {
+        assert!(PositionalSlice::new(0, 1) < PositionalSlice::new(1, 1));
+        assert!(PositionalSlice::new(0, 1) < PositionalSlice::new(0, 2));
+        assert!(PositionalSlice::new(1, 0) > PositionalSlice::new(0, 9));
+    }
+
+    #[test]
+    fn TEST_PositionalSlice_offset_unchecked() {
+        assert_eq!(
+            PositionalSlice::new(1, 1),
+            PositionalSlice::new(0, 1).offset_unchecked(1)
+        );
+        assert_eq!(
+            PositionalSlice::new(0, 1),
+            PositionalSlice::new(1, 1).offset_unchecked(-1)
+        );
+    }
+
+    #[test]
+    fn TEST_PositionalSlice_offset_checked() {
+        assert_eq!(
+            Some(PositionalSlice::new(1, 1)),
+            PositionalSlice::new(0, 1).offset_checked(1)
+        );
+        assert_eq!(
+            Some(PositionalSlice::new(0, 1)),
+            PositionalSlice::new(1, 1).offset_checked(-1)
+        );
+        assert_eq!(None, PositionalSlice::new(0, 1).offset_checked(-1));
+        assert_eq!(
+            Some(PositionalSlice::new(usize::MAX - 1, 1)),
+            PositionalSlice::new(usize::MAX - 2, 1).offset_checked(1)
+        );
+        assert_eq!(None, PositionalSlice::new(usize::MAX - 2, 1).offset_checked(2));
+    }
+
+    #[test]
+    fn TEST_PositionalSlice_subslice_of_AND_substring_of() {
+        let ps = PositionalSlice::new(2, 2);
+
+        assert_eq!(&[2, 3], ps.subslice_of(&[0, 1, 2, 3, 4]));
+        assert_eq!("cd", ps.substring_of("abcdef"));
+        assert_eq!("de", ps.substring_of(&"abcdef"[1..]));
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
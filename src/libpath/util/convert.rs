@@ -0,0 +1,450 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/convert.rs
+ *
+ * Purpose: Conversion of paths between the UNIX and Windows syntaxes.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+//! Conversion of paths between the UNIX and Windows syntaxes, such as is
+//! needed when passing paths between native Windows programs and those
+//! running under MSYS, Cygwin, or WSL.
+
+use super::{
+    unix,
+    windows,
+};
+
+use alloc::string::String;
+use core::fmt;
+
+
+/// How Windows drives are mapped into the UNIX file-system.
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+#[derive(Hash)]
+pub enum DriveMapping {
+    /// `C:\x` <=> `/c/x`, as in MSYS and Git Bash.
+    #[default]
+    Msys,
+    /// `C:\x` <=> `/mnt/c/x`, as in WSL.
+    Wsl,
+    /// `C:\x` <=> `/cygdrive/c/x`, as in Cygwin.
+    Cygwin,
+}
+
+// Non-mutating methods
+impl DriveMapping {
+    /// The directory under which the drives are mounted, including the
+    /// trailing separator, e.g. `"/mnt/"`.
+    pub fn mount_prefix(&self) -> &'static str {
+        match self {
+            Self::Msys => "/",
+            Self::Wsl => "/mnt/",
+            Self::Cygwin => "/cygdrive/",
+        }
+    }
+
+    /// The flag that causes [`unix::path_classify()`] to recognise paths
+    /// within the mounted drives, e.g.
+    /// [`RECOGNISE_WSL_DRIVES`](unix::classification_flags::RECOGNISE_WSL_DRIVES).
+    pub fn unix_classification_flag(&self) -> i32 {
+        use unix::classification_flags::*;
+
+        match self {
+            Self::Msys => RECOGNISE_MSYS_DRIVES,
+            Self::Wsl => RECOGNISE_WSL_DRIVES,
+            Self::Cygwin => RECOGNISE_CYGWIN_DRIVES,
+        }
+    }
+}
+
+
+/// An error in converting a path from one syntax to the other.
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub enum ConversionError {
+    /// The path is a Windows device path, such as `\\.\COM1`.
+    DevicePath,
+    /// The path is relative to the current directory of a Windows drive,
+    /// such as `C:dir`.
+    DriveRelative,
+    /// The path is rooted in the current Windows drive, such as `\dir`.
+    CurrentDriveRooted,
+    /// The path is a UNC path that lacks a server or share name, such as
+    /// `\\server`.
+    IncompleteUnc,
+    /// The path is an absolute UNIX path that is not within the mounted
+    /// drives of the drive mapping, such as `/usr/bin`.
+    NotUnderDriveMount,
+    /// The path contains a character that cannot be represented in the
+    /// target syntax, at the given byte offset.
+    InvalidChar(usize),
+    /// The path is not of a form that can be converted.
+    Unsupported,
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(
+        &self,
+        f : &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::DevicePath => f.write_str("device paths cannot be converted"),
+            Self::DriveRelative => f.write_str("drive-relative paths cannot be converted"),
+            Self::CurrentDriveRooted => f.write_str("paths rooted in the current drive cannot be converted"),
+            Self::IncompleteUnc => f.write_str("incomplete UNC paths cannot be converted"),
+            Self::NotUnderDriveMount => f.write_str("path is not within a mounted drive"),
+            Self::InvalidChar(offset) => write!(f, "path contains an unrepresentable character at offset {offset}"),
+            Self::Unsupported => f.write_str("path cannot be converted"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConversionError {
+}
+
+
+/// Converts a Windows path into the equivalent UNIX path.
+///
+/// Drive-rooted paths are mapped according to `mapping`, UNC paths
+/// `\\server\share\x` become `//server/share/x`, and `\\?\` prefixes are
+/// removed. Relative and home-rooted paths have their separators
+/// converted.
+///
+/// # Parameters:
+/// - `path` - the Windows path to be converted;
+/// - `mapping` - how drives are mapped into the UNIX file-system;
+///
+/// # Errors:
+/// A [`ConversionError`] if the path cannot be represented as a UNIX path.
+pub fn windows_to_unix(
+    path : &str,
+    mapping : DriveMapping,
+) -> Result<String, ConversionError> {
+    use windows::Classification as WC;
+
+    let (cl, cr) = windows::path_classify(path, windows::classification_flags::IGNORE_INVALID_CHARS);
+
+    if let Some(index) = path.find('\0') {
+        return Err(ConversionError::InvalidChar(index));
+    }
+
+    let root = cr.Root.substring_of(path);
+    let rest = &path[cr.Root.offset + cr.Root.len()..];
+    let unprefixed_root = &root[cr.Prefix.len()..];
+
+    let mut converted = String::with_capacity(path.len() + mapping.mount_prefix().len());
+
+    match cl {
+        WC::Empty => (),
+        WC::Relative | WC::HomeRooted => converted.push_str(root),
+        WC::DriveLetterRooted => {
+            let drive = unprefixed_root.chars().next().unwrap_or('c');
+
+            converted.push_str(mapping.mount_prefix());
+            converted.push(drive.to_ascii_lowercase());
+        },
+        WC::UncRooted | WC::WslRooted => {
+            // the server and share names, with the leading separators (or
+            // the "\\?\UNC\" prefix) removed
+
+            let names = if cr.Prefix.is_empty() {
+                &unprefixed_root[2..]
+            } else {
+                unprefixed_root
+            };
+
+            converted.push_str("//");
+            push_converted_(&mut converted, names, '/');
+        },
+        WC::DriveLetterRelative => return Err(ConversionError::DriveRelative),
+        WC::SlashRooted => return Err(ConversionError::CurrentDriveRooted),
+        WC::UncIncomplete => return Err(ConversionError::IncompleteUnc),
+        WC::DeviceRooted => return Err(ConversionError::DevicePath),
+        _ => return Err(ConversionError::Unsupported),
+    }
+
+    push_converted_(&mut converted, rest, '/');
+
+    Ok(converted)
+}
+
+/// Converts a UNIX path into the equivalent Windows path.
+///
+/// Paths within the mounted drives of `mapping` become drive-rooted paths,
+/// `//server/share/x` becomes `\\server\share\x`, and relative and
+/// home-rooted paths have their separators converted.
+///
+/// # Parameters:
+/// - `path` - the UNIX path to be converted;
+/// - `mapping` - how drives are mapped into the UNIX file-system;
+///
+/// # Errors:
+/// A [`ConversionError`] if the path cannot be represented as a Windows
+/// path, including if any of its names contain a character that is
+/// invalid in Windows, such as `'\'` or `':'`.
+pub fn unix_to_windows(
+    path : &str,
+    mapping : DriveMapping,
+) -> Result<String, ConversionError> {
+    use unix::Classification as UC;
+
+    let parse_flags = unix::classification_flags::IGNORE_INVALID_CHARS | mapping.unix_classification_flag();
+
+    let (cl, cr) = unix::path_classify(path, parse_flags);
+
+    let mut root_len = cr.Root.len();
+
+    let mut converted = String::with_capacity(path.len());
+
+    match cl {
+        UC::Empty => (),
+        UC::Relative | UC::HomeRooted => converted.push_str(cr.Root.substring_of(path)),
+        UC::SlashRooted => {
+            if let Some(server_and_share_len) = unc_names_length_(path) {
+                converted.push_str(r"\\");
+                push_converted_(&mut converted, &path[2..2 + server_and_share_len], '\\');
+
+                root_len = 2 + server_and_share_len;
+            } else if path.starts_with("//") && !path.starts_with("///") {
+                return Err(ConversionError::IncompleteUnc);
+            } else {
+                return Err(ConversionError::NotUnderDriveMount);
+            }
+        },
+        UC::ForeignDriveRooted => {
+            let drive = unix::foreign_drive_letter(path, cl, &cr).unwrap_or('c');
+
+            converted.push(drive.to_ascii_uppercase());
+            converted.push(':');
+
+            if root_len == path.len() {
+                converted.push('\\');
+            }
+        },
+        _ => return Err(ConversionError::Unsupported),
+    }
+
+    let rest = &path[root_len..];
+
+    if let Some((index, _)) = rest
+        .char_indices()
+        .find(|&(_, c)| '\\' == c || windows_char_is_invalid_(c))
+    {
+        return Err(ConversionError::InvalidChar(root_len + index));
+    }
+
+    push_converted_(&mut converted, rest, '\\');
+
+    Ok(converted)
+}
+
+
+/// Appends `s` to `converted`, replacing every path-name separator of
+/// either syntax with `separator`.
+fn push_converted_(
+    converted : &mut String,
+    s : &str,
+    separator : char,
+) {
+    converted.extend(s.chars().map(|c| if '/' == c || '\\' == c { separator } else { c }));
+}
+
+/// Obtains the length of the `"server/share"` part of a UNIX path of the
+/// form `"//server/share[/...]"`, if it is of that form.
+fn unc_names_length_(path : &str) -> Option<usize> {
+    let names = path.strip_prefix("//")?;
+
+    let server_len = names.find('/')?;
+
+    if 0 == server_len {
+        return None;
+    }
+
+    let share = &names[server_len + 1..];
+    let share_len = share.find('/').unwrap_or(share.len());
+
+    if 0 == share_len {
+        return None;
+    }
+
+    Some(server_len + 1 + share_len)
+}
+
+/// Evaluates whether a character is invalid in a Windows name.
+fn windows_char_is_invalid_(c : char) -> bool {
+    use super::syntax::PathSyntax;
+
+    windows::WindowsSyntax::char_is_invalid(c)
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+
+    #[test]
+    fn TEST_windows_to_unix() {
+        let cases = [
+            (r"C:\dir\file.ext", DriveMapping::Msys, "/c/dir/file.ext"),
+            (r"C:\dir\file.ext", DriveMapping::Wsl, "/mnt/c/dir/file.ext"),
+            (r"C:\dir\file.ext", DriveMapping::Cygwin, "/cygdrive/c/dir/file.ext"),
+            (r"d:/dir\file.ext", DriveMapping::Msys, "/d/dir/file.ext"),
+            (r"C:\", DriveMapping::Msys, "/c/"),
+            (
+                r"\\server\share\dir\file.ext",
+                DriveMapping::Msys,
+                "//server/share/dir/file.ext",
+            ),
+            (r"\\server\share", DriveMapping::Msys, "//server/share"),
+            (r"\\?\C:\dir\file.ext", DriveMapping::Msys, "/c/dir/file.ext"),
+            (
+                r"\\?\UNC\server\share\file.ext",
+                DriveMapping::Msys,
+                "//server/share/file.ext",
+            ),
+            (r"\\wsl$\Ubuntu\home", DriveMapping::Wsl, "//wsl$/Ubuntu/home"),
+            (r"dir\sub-dir\file.ext", DriveMapping::Msys, "dir/sub-dir/file.ext"),
+            (r"..\file.ext", DriveMapping::Msys, "../file.ext"),
+            (r"~\file.ext", DriveMapping::Msys, "~/file.ext"),
+            (r"what?.ext", DriveMapping::Msys, "what?.ext"),
+            (r"", DriveMapping::Msys, ""),
+        ];
+
+        for (path, mapping, expected) in cases {
+            assert_eq!(
+                Ok(String::from(expected)),
+                windows_to_unix(path, mapping),
+                "path='{path}'"
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_windows_to_unix_WITH_UNREPRESENTABLE_PATHS() {
+        let cases = [
+            (r"\\.\COM1", ConversionError::DevicePath),
+            (r"\\?\Volume{01234567}\dir", ConversionError::DevicePath),
+            (r"C:dir\file.ext", ConversionError::DriveRelative),
+            (r"C:", ConversionError::DriveRelative),
+            (r"\dir\file.ext", ConversionError::CurrentDriveRooted),
+            (r"\\server", ConversionError::IncompleteUnc),
+            ("dir\\fi\0le", ConversionError::InvalidChar(6)),
+        ];
+
+        for (path, expected) in cases {
+            assert_eq!(
+                Err(expected),
+                windows_to_unix(path, DriveMapping::Msys),
+                "path='{path}'"
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_unix_to_windows() {
+        let cases = [
+            ("/c/dir/file.ext", DriveMapping::Msys, r"C:\dir\file.ext"),
+            ("/mnt/c/dir/file.ext", DriveMapping::Wsl, r"C:\dir\file.ext"),
+            ("/cygdrive/c/dir/file.ext", DriveMapping::Cygwin, r"C:\dir\file.ext"),
+            ("/d", DriveMapping::Msys, r"D:\"),
+            ("/d/", DriveMapping::Msys, r"D:\"),
+            (
+                "//server/share/dir/file.ext",
+                DriveMapping::Msys,
+                r"\\server\share\dir\file.ext",
+            ),
+            ("//server/share", DriveMapping::Msys, r"\\server\share"),
+            ("dir/sub-dir/file.ext", DriveMapping::Msys, r"dir\sub-dir\file.ext"),
+            ("../file.ext", DriveMapping::Msys, r"..\file.ext"),
+            ("~/file.ext", DriveMapping::Msys, r"~\file.ext"),
+            ("", DriveMapping::Msys, ""),
+        ];
+
+        for (path, mapping, expected) in cases {
+            assert_eq!(
+                Ok(String::from(expected)),
+                unix_to_windows(path, mapping),
+                "path='{path}'"
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_unix_to_windows_WITH_UNREPRESENTABLE_PATHS() {
+        let cases = [
+            ("/usr/bin", DriveMapping::Msys, ConversionError::NotUnderDriveMount),
+            ("/c/dir", DriveMapping::Wsl, ConversionError::NotUnderDriveMount),
+            ("/mnt/cd/dir", DriveMapping::Wsl, ConversionError::NotUnderDriveMount),
+            ("//server", DriveMapping::Msys, ConversionError::IncompleteUnc),
+            ("//server/", DriveMapping::Msys, ConversionError::IncompleteUnc),
+            ("dir/what?", DriveMapping::Msys, ConversionError::InvalidChar(8)),
+            ("dir/a:b", DriveMapping::Msys, ConversionError::InvalidChar(5)),
+            ("/c/dir/a\\b", DriveMapping::Msys, ConversionError::InvalidChar(8)),
+            ("dir/fi\0le", DriveMapping::Msys, ConversionError::InvalidChar(6)),
+        ];
+
+        for (path, mapping, expected) in cases {
+            assert_eq!(Err(expected), unix_to_windows(path, mapping), "path='{path}'");
+        }
+    }
+
+    #[test]
+    fn TEST_ROUNDTRIP() {
+        for mapping in [DriveMapping::Msys, DriveMapping::Wsl, DriveMapping::Cygwin] {
+            for path in [r"C:\dir\file.ext", r"\\server\share\file.ext", r"dir\file.ext"] {
+                let unix_path = windows_to_unix(path, mapping).unwrap();
+
+                assert_eq!(
+                    Ok(String::from(path)),
+                    unix_to_windows(&unix_path, mapping),
+                    "path='{path}'"
+                );
+            }
+        }
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
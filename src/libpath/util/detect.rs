@@ -0,0 +1,269 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/detect.rs
+ *
+ * Purpose: Classification of paths whose syntax is not known in advance.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+//! Classification of paths whose syntax is not known in advance, such as
+//! those gathered from the logs of a mixture of UNIX and Windows hosts.
+
+use super::{
+    common::ClassificationResult,
+    syntax::Syntax,
+    unix,
+    windows,
+};
+
+
+/// The degree of confidence in the syntax chosen by [`classify_any()`].
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Confidence {
+    /// The path is valid, and equally plausible, in either syntax, e.g.
+    /// `"file.ext"`, and the choice is a default.
+    Low,
+    /// The path is plausible in either syntax but has features that are
+    /// far more likely in one of them, e.g. `"dir\file.ext"`.
+    Medium,
+    /// The path has features that are meaningful only in one syntax, e.g.
+    /// `"C:\dir"` or `"\\server\share"`.
+    High,
+}
+
+
+/// The classification of a path according to the syntax chosen by
+/// [`classify_any()`].
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Classification {
+    Unix(unix::Classification),
+    Windows(windows::Classification),
+}
+
+// Non-mutating methods
+impl Classification {
+    /// The syntax according to which the path was classified.
+    pub fn syntax(&self) -> Syntax {
+        match self {
+            Self::Unix(_) => Syntax::Unix,
+            Self::Windows(_) => Syntax::Windows,
+        }
+    }
+}
+
+
+/// Determines the most likely syntax of `path`, and the confidence in that
+/// determination.
+///
+/// # Note:
+/// Where there is no evidence either way the syntax is [`Syntax::Unix`],
+/// with [`Confidence::Low`].
+pub fn detect_syntax(
+    path : &str
+) -> (
+    Syntax,     // syntax
+    Confidence, // confidence
+) {
+    let bytes = path.as_bytes();
+
+    let has_backslash = bytes.contains(&b'\\');
+    let has_slash = bytes.contains(&b'/');
+
+    // drive-letter roots, e.g. "C:\dir" or "C:dir"
+
+    if let [c0, b':', rest @ ..] = bytes {
+        if c0.is_ascii_alphabetic() {
+            return match rest {
+                [] | [b'\\' | b'/', ..] => (Syntax::Windows, Confidence::High),
+                _ => (Syntax::Windows, Confidence::Medium),
+            };
+        }
+    }
+
+    // UNC and device paths, e.g. "\\server\share" or "\\?\C:\dir"
+
+    if bytes.starts_with(b"\\\\") {
+        return (Syntax::Windows, Confidence::High);
+    }
+
+    if has_backslash {
+        if has_slash {
+            return (Syntax::Windows, Confidence::Low);
+        } else {
+            return (Syntax::Windows, Confidence::Medium);
+        }
+    }
+
+    if bytes.starts_with(b"/") {
+        return (Syntax::Unix, Confidence::High);
+    }
+
+    if has_slash {
+        return (Syntax::Unix, Confidence::Medium);
+    }
+
+    // a name that could not be used on Windows
+
+    if path
+        .chars()
+        .any(|c| matches!(c, '<' | '>' | ':' | '"' | '|' | '?' | '*'))
+    {
+        return (Syntax::Unix, Confidence::Medium);
+    }
+
+    (Syntax::Unix, Confidence::Low)
+}
+
+/// Classifies a path of unknown syntax, according to the syntax determined
+/// by [`detect_syntax()`].
+///
+/// # Parameters:
+/// - `path` - the given path to be classified;
+/// - `parse_flags` - flags that moderate the classification, which should
+///   be those that have the same meaning for both syntaxes - see
+///   [`syntax::classification_flags`](super::syntax::classification_flags);
+///
+/// # Returns:
+/// `(classification : Classification, confidence : Confidence, classification_result : ClassificationResult)`,
+/// where the chosen syntax is available as `classification.syntax()`.
+pub fn classify_any(
+    path : &str,
+    parse_flags : i32,
+) -> (
+    Classification,       // classification
+    Confidence,           // confidence
+    ClassificationResult, // classification_result
+) {
+    let (syntax, confidence) = detect_syntax(path);
+
+    match syntax {
+        Syntax::Unix => {
+            let (cl, cr) = unix::path_classify(path, parse_flags);
+
+            (Classification::Unix(cl), confidence, cr)
+        },
+        Syntax::Windows => {
+            let (cl, cr) = windows::path_classify(path, parse_flags);
+
+            (Classification::Windows(cl), confidence, cr)
+        },
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+
+    #[test]
+    fn TEST_detect_syntax() {
+        let cases = [
+            (r"C:\dir\file.ext", Syntax::Windows, Confidence::High),
+            (r"C:/dir/file.ext", Syntax::Windows, Confidence::High),
+            (r"C:", Syntax::Windows, Confidence::High),
+            (r"C:file.ext", Syntax::Windows, Confidence::Medium),
+            (r"\\server\share\file.ext", Syntax::Windows, Confidence::High),
+            (r"\\?\C:\dir", Syntax::Windows, Confidence::High),
+            (r"\dir\file.ext", Syntax::Windows, Confidence::Medium),
+            (r"dir\file.ext", Syntax::Windows, Confidence::Medium),
+            (r"dir\sub-dir/file.ext", Syntax::Windows, Confidence::Low),
+            (r"/dir/file.ext", Syntax::Unix, Confidence::High),
+            (r"//server/share", Syntax::Unix, Confidence::High),
+            (r"dir/file.ext", Syntax::Unix, Confidence::Medium),
+            (r"~/file.ext", Syntax::Unix, Confidence::Medium),
+            (r"what?", Syntax::Unix, Confidence::Medium),
+            (r"file.ext", Syntax::Unix, Confidence::Low),
+            (r"", Syntax::Unix, Confidence::Low),
+        ];
+
+        for (path, expected_syntax, expected_confidence) in cases {
+            assert_eq!(
+                (expected_syntax, expected_confidence),
+                detect_syntax(path),
+                "path='{path}'"
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_classify_any_WITH_WINDOWS_PATH() {
+        let path = r"C:\dir\file.ext";
+        let (cl, confidence, cr) = classify_any(path, 0);
+
+        assert_eq!(Classification::Windows(windows::Classification::DriveLetterRooted), cl);
+        assert_eq!(Syntax::Windows, cl.syntax());
+        assert_eq!(Confidence::High, confidence);
+        assert_eq!(windows::path_classify(path, 0).1, cr);
+        assert_eq!("C:", cr.Root.substring_of(path));
+        assert_eq!("file.ext", cr.EntryName.substring_of(path));
+    }
+
+    #[test]
+    fn TEST_classify_any_WITH_UNIX_PATH() {
+        let path = "/dir/file.ext";
+        let (cl, confidence, cr) = classify_any(path, 0);
+
+        assert_eq!(Classification::Unix(unix::Classification::SlashRooted), cl);
+        assert_eq!(Syntax::Unix, cl.syntax());
+        assert_eq!(Confidence::High, confidence);
+        assert_eq!(unix::path_classify(path, 0).1, cr);
+        assert_eq!("/dir/", cr.Directory.substring_of(path));
+    }
+
+    #[test]
+    fn TEST_classify_any_WITH_AMBIGUOUS_PATH() {
+        let path = "file.ext";
+        let (cl, confidence, cr) = classify_any(path, 0);
+
+        assert_eq!(Classification::Unix(unix::Classification::Relative), cl);
+        assert_eq!(Confidence::Low, confidence);
+        assert_eq!(".ext", cr.Extension.substring_of(path));
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
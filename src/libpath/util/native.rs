@@ -0,0 +1,166 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/native.rs
+ *
+ * Purpose: Classification according to the syntax of the host platform.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+//! Classification according to the syntax of the host platform.
+//!
+//! On Windows this re-exports from [`windows`](super::windows), and
+//! everywhere else from [`unix`](super::unix).
+
+#[cfg(not(windows))]
+pub use super::unix::{
+    classification_flags,
+    Classification,
+    ClassifiedPath,
+};
+#[cfg(windows)]
+pub use super::windows::{
+    classification_flags,
+    Classification,
+    ClassifiedPath,
+};
+
+use super::common::{
+    ClassificationResult,
+    PathNotUnicodeError,
+};
+
+use std::path::Path;
+
+
+/// Classifies `path` according to the syntax of the host platform.
+///
+/// On UNIX the path is classified as bytes, so any path obtained from the
+/// operating system may be classified, and the positions in the result are
+/// offsets into `path.as_os_str().as_encoded_bytes()`. Elsewhere the path
+/// is classified as a string.
+///
+/// # Parameters:
+/// - `path` - the given path to be classified;
+/// - `parse_flags` - flags that moderate the classification;
+///
+/// # Errors:
+/// [`PathNotUnicodeError`] if, other than on UNIX, `path` is not valid
+/// Unicode.
+pub fn path_classify(
+    path : &Path,
+    parse_flags : i32,
+) -> Result<
+    (
+        Classification,       // classification
+        ClassificationResult, // classification_result
+    ),
+    PathNotUnicodeError,
+> {
+    #[cfg(unix)]
+    {
+        Ok(super::unix::path_classify_os_str(path.as_os_str(), parse_flags))
+    }
+
+    #[cfg(not(unix))]
+    {
+        #[cfg(not(windows))]
+        use super::unix::path_classify as platform_path_classify;
+        #[cfg(windows)]
+        use super::windows::path_classify as platform_path_classify;
+
+        match path.to_str() {
+            Some(s) => Ok(platform_path_classify(s, parse_flags)),
+            None => Err(PathNotUnicodeError),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+
+    #[test]
+    fn TEST_path_classify_WITH_EMPTY_INPUT() {
+        let (cl, cr) = path_classify(Path::new(""), 0).unwrap();
+
+        assert_eq!(Classification::Empty, cl);
+        assert_eq!(ClassificationResult::empty(), cr);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn TEST_path_classify_ON_NON_WINDOWS() {
+        let path = Path::new("/dir/file.ext");
+        let (cl, cr) = path_classify(path, 0).unwrap();
+
+        assert_eq!(Classification::SlashRooted, cl);
+        assert_eq!("file.ext", cr.EntryName.substring_of(path.to_str().unwrap()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn TEST_path_classify_WITH_NON_UNICODE_PATH_ON_UNIX() {
+        use std::{
+            ffi::OsStr,
+            os::unix::ffi::OsStrExt,
+        };
+
+        let bytes = b"/dir/file-\xff.ext";
+        let path = Path::new(OsStr::from_bytes(bytes));
+        let (cl, cr) = path_classify(path, 0).unwrap();
+
+        assert_eq!(Classification::SlashRooted, cl);
+        assert_eq!(b"file-\xff.ext", cr.EntryName.subslice_of(bytes));
+        assert_eq!(b".ext", cr.Extension.subslice_of(bytes));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn TEST_path_classify_ON_WINDOWS() {
+        let path = Path::new(r"C:\dir\file.ext");
+        let (cl, cr) = path_classify(path, 0).unwrap();
+
+        assert_eq!(Classification::DriveLetterRooted, cl);
+        assert_eq!("file.ext", cr.EntryName.substring_of(path.to_str().unwrap()));
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
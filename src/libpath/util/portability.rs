@@ -0,0 +1,614 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/portability.rs
+ *
+ * Purpose: Checking of paths for portability between UNIX and Windows.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+//! Checking of paths for portability between UNIX and Windows, such as
+//! those of the files in a repository that is checked out on both.
+
+use super::{
+    common::PositionalSlice as PoSl,
+    syntax::{
+        PathSyntax,
+        Syntax,
+    },
+    unix::{
+        self,
+        UnixSyntax,
+    },
+    windows::{
+        self,
+        WindowsSyntax,
+    },
+};
+
+use alloc::vec::Vec;
+use core::{
+    cmp::Reverse,
+    fmt,
+};
+
+
+/// The maximum length, in bytes, of a portable name.
+///
+/// This is `NAME_MAX` on most UNIX file-systems, and is also within the
+/// limit (of 255 UTF-16 code units) of NTFS.
+pub const MAX_NAME_LENGTH : usize = 255;
+
+/// The maximum length, in UTF-16 code units, of a portable path.
+///
+/// This is `MAX_PATH` (260) less the terminating NUL, which is the limit
+/// of Windows programs that do not opt in to long paths.
+pub const MAX_PATH_LENGTH : usize = 259;
+
+
+/// The kind of a [`PortabilityProblem`].
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub enum PortabilityProblemKind {
+    /// The root is interpreted differently by UNIX and Windows, e.g.
+    /// `"C:"` or `"\\server\share"`.
+    RootNotPortable,
+    /// A `'\'`, which is a separator on Windows but a name character on
+    /// UNIX.
+    BackslashSeparator,
+    /// A character that is invalid in either syntax, such as `':'` or
+    /// `'\0'`.
+    InvalidChar(char),
+    /// A character that is valid in both syntaxes but is outside of the
+    /// POSIX portable filename character set (`A-Za-z0-9._-`).
+    NonPortableChar(char),
+    /// A name that begins with `'-'`, which POSIX advises against.
+    LeadingHyphen,
+    /// A name that ends with one or more dots or spaces, which are removed
+    /// by Windows.
+    TrailingDotOrSpace,
+    /// A name that is reserved on Windows, such as `"aux.rs"`.
+    ReservedName,
+    /// A name that is longer than [`MAX_NAME_LENGTH`] bytes.
+    NameTooLong,
+    /// A path that is longer than [`MAX_PATH_LENGTH`] UTF-16 code units.
+    PathTooLong,
+}
+
+impl fmt::Display for PortabilityProblemKind {
+    fn fmt(
+        &self,
+        f : &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::RootNotPortable => f.write_str("root is not portable"),
+            Self::BackslashSeparator => f.write_str("backslash is not a separator on UNIX"),
+            Self::InvalidChar(c) => write!(f, "invalid character {c:?}"),
+            Self::NonPortableChar(c) => {
+                write!(f, "character {c:?} is not in the POSIX portable filename character set")
+            },
+            Self::LeadingHyphen => f.write_str("name begins with a hyphen"),
+            Self::TrailingDotOrSpace => f.write_str("name ends with a dot or a space"),
+            Self::ReservedName => f.write_str("name is reserved on Windows"),
+            Self::NameTooLong => write!(f, "name is longer than {MAX_NAME_LENGTH} bytes"),
+            Self::PathTooLong => write!(f, "path is longer than {MAX_PATH_LENGTH} UTF-16 code units"),
+        }
+    }
+}
+
+
+/// A problem found by [`check()`] or [`check_for_syntax()`].
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub struct PortabilityProblem {
+    /// The kind of problem.
+    pub kind :     PortabilityProblemKind,
+    /// The position, in bytes, of the offending character, name, or path.
+    pub position : PoSl,
+}
+
+
+/// The result of [`check()`] or [`check_for_syntax()`].
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+pub struct PortabilityReport {
+    /// The problems found, in order of position, with, of those at the
+    /// same position, any that span more of the path - such as
+    /// [`PortabilityProblemKind::PathTooLong`] - first.
+    pub problems : Vec<PortabilityProblem>,
+}
+
+// Non-mutating methods
+impl PortabilityReport {
+    /// Indicates whether no problems were found.
+    pub fn is_portable(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    /// Indicates whether no problems were found, other than
+    /// [`PortabilityProblemKind::NonPortableChar`] and
+    /// [`PortabilityProblemKind::LeadingHyphen`], which are advisory.
+    pub fn is_portable_ignoring_posix_advice(&self) -> bool {
+        self.problems.iter().all(|p| {
+            matches!(
+                p.kind,
+                PortabilityProblemKind::NonPortableChar(_) | PortabilityProblemKind::LeadingHyphen
+            )
+        })
+    }
+}
+
+
+/// Checks a path for portability between UNIX and Windows.
+///
+/// The path is classified according to both syntaxes, and its root (if
+/// any) and each of its names are checked against the rules of both, and
+/// against the POSIX portable filename character set and the length limits
+/// [`MAX_NAME_LENGTH`] and [`MAX_PATH_LENGTH`].
+///
+/// # Parameters:
+/// - `path` - the path to be checked;
+///
+/// # Returns:
+/// A [`PortabilityReport`] listing every problem found.
+pub fn check(path : &str) -> PortabilityReport {
+    check_(path, true, true)
+}
+
+/// Checks a path for portability within a single syntax.
+///
+/// The path is classified according to `syntax`, and each of its names is
+/// checked against the rules of that syntax only, and against the POSIX
+/// portable filename character set and [`MAX_NAME_LENGTH`]. So, for
+/// example, `"dir/CON.txt"` is portable for UNIX, but not for Windows, and
+/// a `'\'` is reported as a
+/// [`NonPortableChar`](PortabilityProblemKind::NonPortableChar) for UNIX,
+/// rather than as a
+/// [`BackslashSeparator`](PortabilityProblemKind::BackslashSeparator).
+///
+/// [`PortabilityProblemKind::RootNotPortable`] and
+/// [`PortabilityProblemKind::BackslashSeparator`] are never reported, and
+/// [`PortabilityProblemKind::TrailingDotOrSpace`],
+/// [`PortabilityProblemKind::ReservedName`], and
+/// [`PortabilityProblemKind::PathTooLong`] are reported only for Windows.
+///
+/// # Parameters:
+/// - `path` - the path to be checked;
+/// - `syntax` - the syntax by which the path is interpreted;
+///
+/// # Returns:
+/// A [`PortabilityReport`] listing every problem found.
+pub fn check_for_syntax(
+    path : &str,
+    syntax : Syntax,
+) -> PortabilityReport {
+    match syntax {
+        Syntax::Unix => check_(path, true, false),
+        Syntax::Windows => check_(path, false, true),
+    }
+}
+
+
+/// Checks a path according to UNIX, if `for_unix`, and to Windows, if
+/// `for_windows`.
+///
+/// # Preconditions:
+/// * at least one of `for_unix` and `for_windows` is `true`;
+fn check_(
+    path : &str,
+    for_unix : bool,
+    for_windows : bool,
+) -> PortabilityReport {
+    use PortabilityProblemKind::*;
+
+    debug_assert!(for_unix || for_windows);
+
+    let mut problems = Vec::new();
+
+    let (_, unix_cr) = unix::path_classify(path, unix::classification_flags::IGNORE_INVALID_CHARS);
+    let (_, windows_cr) = windows::path_classify(path, windows::classification_flags::IGNORE_INVALID_CHARS);
+
+    // root
+
+    let root_len = match (for_unix, for_windows) {
+        (true, false) => unix_cr.Root.len(),
+        (false, true) => windows_cr.Root.len(),
+        _ => unix_cr.Root.len().max(windows_cr.Root.len()),
+    };
+
+    if for_unix && for_windows && unix_cr.Root != windows_cr.Root {
+        problems.push(PortabilityProblem {
+            kind :     RootNotPortable,
+            position : PoSl::new(0, root_len),
+        });
+    }
+
+    // names
+
+    let char_is_separator = |c : char| {
+        if for_windows {
+            WindowsSyntax::char_is_path_name_separator(c)
+        } else {
+            UnixSyntax::char_is_path_name_separator(c)
+        }
+    };
+
+    let mut name_start = root_len;
+
+    for (index, c) in path[root_len..].char_indices().map(|(i, c)| (root_len + i, c)) {
+        if char_is_separator(c) {
+            check_name_(path, name_start, index, for_unix, for_windows, &mut problems);
+
+            if for_unix && !UnixSyntax::char_is_path_name_separator(c) {
+                problems.push(PortabilityProblem {
+                    kind :     BackslashSeparator,
+                    position : PoSl::new(index, 1),
+                });
+            }
+
+            name_start = index + 1;
+        }
+    }
+
+    check_name_(path, name_start, path.len(), for_unix, for_windows, &mut problems);
+
+    // path
+
+    let path_len = path.encode_utf16().count();
+
+    if for_windows && path_len > MAX_PATH_LENGTH {
+        problems.push(PortabilityProblem {
+            kind :     PathTooLong,
+            position : PoSl::new(0, path.len()),
+        });
+    }
+
+    problems.sort_by_key(|p| (p.position.offset, Reverse(p.position.length)));
+
+    PortabilityReport {
+        problems,
+    }
+}
+
+/// Checks the name at `path[from..to]`, which contains no separators,
+/// according to UNIX, if `for_unix`, and to Windows, if `for_windows`.
+fn check_name_(
+    path : &str,
+    from : usize,
+    to : usize,
+    for_unix : bool,
+    for_windows : bool,
+    problems : &mut Vec<PortabilityProblem>,
+) {
+    use PortabilityProblemKind::*;
+
+    let name = &path[from..to];
+
+    if name.is_empty() || "." == name || ".." == name {
+        return;
+    }
+
+    if name.starts_with('-') {
+        problems.push(PortabilityProblem {
+            kind :     LeadingHyphen,
+            position : PoSl::new(from, 1),
+        });
+    }
+
+    for (index, c) in name.char_indices() {
+        let kind = if (for_unix && UnixSyntax::char_is_invalid(c)) || (for_windows && WindowsSyntax::char_is_invalid(c))
+        {
+            InvalidChar(c)
+        } else if !char_is_posix_portable_(c) {
+            NonPortableChar(c)
+        } else {
+            continue;
+        };
+
+        problems.push(PortabilityProblem {
+            kind,
+            position : PoSl::new(from + index, c.len_utf8()),
+        });
+    }
+
+    // the reserved names are checked without the trailing dots and spaces,
+    // which Windows removes, so that, e.g., "CON." is reported as both
+
+    if for_windows {
+        let trimmed = name.trim_end_matches(['.', ' ']);
+
+        if trimmed.len() != name.len() {
+            problems.push(PortabilityProblem {
+                kind :     TrailingDotOrSpace,
+                position : PoSl::new(from + trimmed.len(), name.len() - trimmed.len()),
+            });
+        }
+
+        if !trimmed.is_empty() && WindowsSyntax::name_is_reserved(trimmed) {
+            problems.push(PortabilityProblem {
+                kind :     ReservedName,
+                position : PoSl::new(from, name.len()),
+            });
+        }
+    }
+
+    if name.len() > MAX_NAME_LENGTH {
+        problems.push(PortabilityProblem {
+            kind :     NameTooLong,
+            position : PoSl::new(from, name.len()),
+        });
+    }
+}
+
+/// Indicates whether `c` is in the POSIX portable filename character set.
+fn char_is_posix_portable_(c : char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    use alloc::{
+        format,
+        string::String,
+        vec,
+    };
+
+
+    fn kinds_and_positions(path : &str) -> Vec<(PortabilityProblemKind, usize, usize)> {
+        check(path)
+            .problems
+            .into_iter()
+            .map(|p| (p.kind, p.position.offset, p.position.length))
+            .collect()
+    }
+
+
+    #[test]
+    fn TEST_check_WITH_PORTABLE_PATHS() {
+        for path in ["", "src/lib.rs", "/usr/local/bin", "./a/../b_c-d.e", "dir/"] {
+            let report = check(path);
+
+            assert!(report.is_portable(), "path='{path}', report={report:?}");
+            assert!(report.is_portable_ignoring_posix_advice(), "path='{path}'");
+        }
+    }
+
+    #[test]
+    fn TEST_check_WITH_RESERVED_NAMES() {
+        use PortabilityProblemKind::*;
+
+        assert_eq!(vec![(ReservedName, 4, 6)], kinds_and_positions("src/aux.rs"));
+        assert_eq!(
+            vec![(ReservedName, 0, 3), (ReservedName, 4, 8)],
+            kinds_and_positions("CON/com1.txt")
+        );
+    }
+
+    #[test]
+    fn TEST_check_WITH_INVALID_CHARS() {
+        use PortabilityProblemKind::*;
+
+        assert_eq!(vec![(InvalidChar(':'), 3, 1)], kinds_and_positions("Foo:bar"));
+        assert_eq!(
+            vec![(InvalidChar('?'), 5, 1), (InvalidChar('\0'), 7, 1)],
+            kinds_and_positions("dir/a?b\0c")
+        );
+    }
+
+    #[test]
+    fn TEST_check_WITH_TRAILING_DOTS_AND_SPACES() {
+        use PortabilityProblemKind::*;
+
+        let report = check("x ");
+
+        assert_eq!(
+            vec![(NonPortableChar(' '), 1, 1), (TrailingDotOrSpace, 1, 1)],
+            kinds_and_positions("x ")
+        );
+        assert!(!report.is_portable_ignoring_posix_advice());
+
+        assert_eq!(vec![(TrailingDotOrSpace, 3, 2)], kinds_and_positions("abc../def"));
+
+        assert_eq!(
+            vec![(TrailingDotOrSpace, 1, 3), (NonPortableChar(' '), 2, 1)],
+            kinds_and_positions("a. .")
+        );
+    }
+
+    #[test]
+    fn TEST_check_WITH_RESERVED_NAMES_WITH_TRAILING_DOTS_AND_SPACES() {
+        use PortabilityProblemKind::*;
+
+        assert_eq!(
+            vec![(ReservedName, 0, 4), (TrailingDotOrSpace, 3, 1)],
+            kinds_and_positions("CON.")
+        );
+        assert_eq!(
+            vec![
+                (ReservedName, 4, 9),
+                (TrailingDotOrSpace, 11, 2),
+                (NonPortableChar(' '), 11, 1)
+            ],
+            kinds_and_positions("dir/aux.txt .")
+        );
+        assert_eq!(vec![(TrailingDotOrSpace, 4, 1)], kinds_and_positions("CONX."));
+    }
+
+    #[test]
+    fn TEST_check_WITH_NON_PORTABLE_CHARS() {
+        use PortabilityProblemKind::*;
+
+        assert_eq!(
+            vec![
+                (LeadingHyphen, 4, 1),
+                (NonPortableChar('é'), 6, 2),
+                (NonPortableChar('+'), 8, 1)
+            ],
+            kinds_and_positions("dir/-cé+")
+        );
+
+        assert!(check("dir/-cé+").is_portable_ignoring_posix_advice());
+    }
+
+    #[test]
+    fn TEST_check_WITH_ROOTS_AND_SEPARATORS() {
+        use PortabilityProblemKind::*;
+
+        assert_eq!(
+            vec![(RootNotPortable, 0, 2), (BackslashSeparator, 2, 1)],
+            kinds_and_positions(r"C:\dir")
+        );
+        assert_eq!(vec![(RootNotPortable, 0, 14)], kinds_and_positions(r"\\server\share"));
+        assert_eq!(vec![(BackslashSeparator, 3, 1)], kinds_and_positions(r"dir\file"));
+    }
+
+    #[test]
+    fn TEST_check_WITH_LONG_NAMES_AND_PATHS() {
+        use PortabilityProblemKind::*;
+
+        let name = "a".repeat(MAX_NAME_LENGTH);
+
+        assert!(check(&name).is_portable());
+
+        let name = "a".repeat(MAX_NAME_LENGTH + 1);
+
+        assert_eq!(vec![(NameTooLong, 0, 256)], kinds_and_positions(&name));
+
+        let mut path = String::new();
+
+        while path.len() <= MAX_PATH_LENGTH {
+            path.push_str("abcdefghi/");
+        }
+
+        assert_eq!(vec![(PathTooLong, 0, path.len())], kinds_and_positions(&path));
+
+        let path = format!("C:/aux/{path}");
+
+        assert_eq!(
+            vec![
+                (PathTooLong, 0, path.len()),
+                (RootNotPortable, 0, 2),
+                (ReservedName, 3, 3)
+            ],
+            kinds_and_positions(&path)
+        );
+    }
+
+    #[test]
+    fn TEST_check_for_syntax() {
+        use PortabilityProblemKind::*;
+
+        let kinds_and_positions = |path : &str, syntax : Syntax| -> Vec<(PortabilityProblemKind, usize, usize)> {
+            check_for_syntax(path, syntax)
+                .problems
+                .into_iter()
+                .map(|p| (p.kind, p.position.offset, p.position.length))
+                .collect()
+        };
+
+        for syntax in [Syntax::Unix, Syntax::Windows] {
+            for path in ["", "src/lib.rs", "./a/../b_c-d.e", "dir/"] {
+                assert!(check_for_syntax(path, syntax).is_portable(), "path='{path}'");
+            }
+        }
+
+        // the root is interpreted in the one syntax
+
+        assert_eq!(
+            vec![(NonPortableChar(':'), 1, 1)],
+            kinds_and_positions("C:/dir", Syntax::Unix)
+        );
+        assert!(check_for_syntax("C:/dir", Syntax::Windows).is_portable());
+        assert!(check_for_syntax(r"\\server\share\dir", Syntax::Windows).is_portable());
+
+        // a backslash is a name character on UNIX, and a separator on
+        // Windows
+
+        assert_eq!(
+            vec![(NonPortableChar('\\'), 3, 1)],
+            kinds_and_positions(r"dir\file", Syntax::Unix)
+        );
+        assert!(check_for_syntax(r"dir\file", Syntax::Windows).is_portable());
+
+        // invalid characters, reserved names, and trailing dots and spaces
+
+        assert_eq!(
+            vec![(NonPortableChar('?'), 5, 1)],
+            kinds_and_positions("dir/a?b", Syntax::Unix)
+        );
+        assert_eq!(
+            vec![(InvalidChar('?'), 5, 1)],
+            kinds_and_positions("dir/a?b", Syntax::Windows)
+        );
+
+        assert!(check_for_syntax("dir/CON.txt", Syntax::Unix).is_portable());
+        assert_eq!(
+            vec![(ReservedName, 4, 7)],
+            kinds_and_positions("dir/CON.txt", Syntax::Windows)
+        );
+
+        assert!(check_for_syntax("abc../def", Syntax::Unix).is_portable());
+        assert_eq!(
+            vec![(TrailingDotOrSpace, 3, 2)],
+            kinds_and_positions("abc../def", Syntax::Windows)
+        );
+
+        // lengths
+
+        let mut path = String::new();
+
+        while path.len() <= MAX_PATH_LENGTH {
+            path.push_str("abcdefghi/");
+        }
+
+        assert!(check_for_syntax(&path, Syntax::Unix).is_portable());
+        assert_eq!(
+            vec![(PathTooLong, 0, path.len())],
+            kinds_and_positions(&path, Syntax::Windows)
+        );
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
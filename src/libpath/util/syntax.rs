@@ -0,0 +1,717 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/syntax.rs
+ *
+ * Purpose: Definition of the PathSyntax trait, and the generic classifier
+ *          that is parameterised by it.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+//! The [`PathSyntax`] trait, and the generic classifier that is
+//! parameterised by it.
+//!
+//! The [`unix`](super::unix) and [`windows`](super::windows) modules are
+//! implemented in terms of this, by [`UnixSyntax`](super::unix::UnixSyntax)
+//! and [`WindowsSyntax`](super::windows::WindowsSyntax) respectively, and
+//! other crates may define further syntaxes by implementing the trait.
+
+#[cfg(feature = "alloc")]
+mod classified_path;
+mod classify_fast;
+mod common_ancestor;
+#[cfg(feature = "alloc")]
+mod compare;
+#[cfg(feature = "alloc")]
+mod containment;
+#[cfg(feature = "alloc")]
+mod join;
+#[cfg(feature = "alloc")]
+mod normalise;
+#[cfg(feature = "alloc")]
+mod path_arena;
+#[cfg(feature = "alloc")]
+mod path_trie;
+#[cfg(feature = "unicode")]
+mod unicode;
+
+#[cfg(feature = "alloc")]
+pub use self::classified_path::ClassifiedPath;
+#[cfg(feature = "alloc")]
+pub use self::compare::{
+    path_cmp,
+    path_key,
+    paths_equal,
+    PathKey,
+};
+#[cfg(feature = "alloc")]
+pub use self::containment::{
+    is_contained_within,
+    ContainmentError,
+};
+#[cfg(feature = "alloc")]
+pub use self::join::{
+    join,
+    relative_path,
+    RelativePathError,
+};
+#[cfg(feature = "alloc")]
+pub use self::normalise::normalise;
+#[cfg(feature = "alloc")]
+pub use self::path_arena::{
+    PathArena,
+    PathHandle,
+};
+#[cfg(feature = "alloc")]
+pub use self::path_trie::{
+    PathTrie,
+    PathTrieIter,
+};
+#[cfg(feature = "unicode")]
+pub use self::unicode::{
+    is_unicode_normalised,
+    normalise_unicode,
+    NormalForm,
+};
+pub use self::{
+    classify_fast::path_classify_fast,
+    common_ancestor::common_ancestor,
+};
+
+use super::common::{
+    ClassificationResult,
+    PositionalSlice as PoSl,
+};
+
+use core::{
+    fmt,
+    hash::Hash,
+};
+
+
+/// Flags that are common to all syntaxes, and that have the same values
+/// in the `classification_flags` module of each syntax.
+pub mod classification_flags {
+
+    /// Runs of path-name separators are not considered invalid.
+    pub const IGNORE_SLASH_RUNS : i32 = 0x00000001;
+    /// Characters for which
+    /// [`PathSyntax::char_is_invalid()`](super::PathSyntax::char_is_invalid)
+    /// returns `true` do not cause the path to be classified as
+    /// [`PathSyntax::INVALID_CHARS`](super::PathSyntax::INVALID_CHARS). The
+    /// position of the first of them is reported in
+    /// `ClassificationResult::FirstInvalid` regardless.
+    pub const IGNORE_INVALID_CHARS : i32 = 0x00000002;
+    /// T.B.C.
+    pub const RECOGNISE_TILDE_HOME : i32 = 0x00000004;
+}
+
+
+/// Flags that moderate the comparison of paths, by [`paths_equal()`],
+/// [`path_cmp()`], and [`path_key()`].
+#[cfg(feature = "alloc")]
+pub mod comparison_flags {
+
+    /// A trailing separator - other than one that is part of a bare root,
+    /// such as `"/"` or `"C:\"` - is ignored, so that `"dir/"` and
+    /// `"dir"` are equal.
+    pub const IGNORE_TRAILING_SEPARATOR : i32 = 0x00000001;
+    /// The paths are compared in their [normalised](super::normalise())
+    /// form, so that `"a/./b/../c"` and `"a/c"` are equal.
+    pub const NORMALISE : i32 = 0x00000002;
+    /// The names are compared in Unicode Normalization Form C, so that
+    /// `"café"` in NFC and in NFD are equal.
+    #[cfg(feature = "unicode")]
+    pub const UNICODE_NORMALISE : i32 = 0x00000004;
+}
+
+
+/// Identifies one of the syntaxes implemented by this crate.
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Syntax {
+    /// The UNIX syntax, as implemented by
+    /// [`UnixSyntax`](super::unix::UnixSyntax).
+    Unix,
+    /// The Windows syntax, as implemented by
+    /// [`WindowsSyntax`](super::windows::WindowsSyntax).
+    Windows,
+}
+
+
+/// A code unit in which a path may be expressed to the generic classifier,
+/// i.e. `u8` (for UTF-8 or arbitrary bytes) or `u16` (for UTF-16).
+pub trait CodeUnit: Copy + Into<u32> {
+    /// Obtains the character represented by the unit, if it is ASCII, or
+    /// `char::REPLACEMENT_CHARACTER` otherwise.
+    fn as_char(self) -> char {
+        let u : u32 = self.into();
+
+        if u < 0x80 {
+            u as u8 as char
+        } else {
+            char::REPLACEMENT_CHARACTER
+        }
+    }
+}
+
+impl CodeUnit for u8 {
+}
+
+impl CodeUnit for u16 {
+}
+
+
+/// Describes a path syntax, such as that of UNIX or of Windows.
+///
+/// The generic classifier operates on code units, rather than on `char`s,
+/// and so a syntax may only give significance - as separator, as part of a
+/// root, or as invalid - to ASCII characters. All other characters are
+/// always part of a name.
+pub trait PathSyntax {
+    /// The classification type of the syntax.
+    type Classification: Copy + Eq + Ord + Hash + fmt::Debug;
+
+    /// The classification of the empty path.
+    const EMPTY : Self::Classification;
+    /// The classification of a path that contains invalid characters.
+    const INVALID_CHARS : Self::Classification;
+    /// The classification of a path that is relative to the current
+    /// directory, and has no root.
+    const RELATIVE : Self::Classification;
+    /// The separator used when forming paths.
+    const PREFERRED_SEPARATOR : char;
+    /// Whether names that differ only in case denote different entries.
+    const IS_CASE_SENSITIVE : bool;
+
+    /// Evaluates whether a character is a path-name separator.
+    fn char_is_path_name_separator(c : char) -> bool;
+
+    /// Evaluates whether a character may not appear in a name.
+    ///
+    /// A path that contains such a character - outside its root - is
+    /// classified as [`INVALID_CHARS`](Self::INVALID_CHARS), unless
+    /// [`ignores_invalid_chars()`](Self::ignores_invalid_chars) returns
+    /// `true` for the path.
+    fn char_is_invalid(c : char) -> bool;
+
+    /// Examines the path to the degree necessary to be able to classify
+    /// it.
+    ///
+    /// # Parameters:
+    /// - `path` - the given path to be classified, which will not be empty;
+    /// - `parse_flags` - flags that moderate the classification;
+    ///
+    /// # Returns:
+    /// `(classification : Classification, root : PositionalSlice, path_root_stripped : PositionalSlice)`
+    fn classify_root<U : CodeUnit>(
+        path : &[U],
+        parse_flags : i32,
+    ) -> (
+        Self::Classification, // classification
+        PoSl,                 // root
+        PoSl,                 // path_root_stripped
+    );
+
+    /// Looks for the last path-name separator in `s`.
+    ///
+    /// The default implementation looks for the last character for which
+    /// [`char_is_path_name_separator()`](Self::char_is_path_name_separator)
+    /// returns `true`.
+    ///
+    /// # Returns:
+    /// The index of the separator, or `None` if `s` contains none.
+    fn find_last_separator<U : CodeUnit>(s : &[U]) -> Option<usize> {
+        s.iter().rposition(|&u| Self::char_is_path_name_separator(u.as_char()))
+    }
+
+    /// Evaluates whether the characters for which
+    /// [`char_is_invalid()`](Self::char_is_invalid) returns `true` are to be
+    /// ignored in the given path, rather than causing it to be classified as
+    /// [`INVALID_CHARS`](Self::INVALID_CHARS).
+    ///
+    /// The default implementation returns `true` if, and only if,
+    /// [`IGNORE_INVALID_CHARS`](classification_flags::IGNORE_INVALID_CHARS)
+    /// is specified.
+    ///
+    /// # Parameters:
+    /// - `path` - the given path to be classified;
+    /// - `parse_flags` - flags that moderate the classification;
+    fn ignores_invalid_chars<U : CodeUnit>(
+        path : &[U],
+        parse_flags : i32,
+    ) -> bool {
+        let _ = path;
+
+        0 != (parse_flags & classification_flags::IGNORE_INVALID_CHARS)
+    }
+
+    /// Obtains the length of the prefix of the path - such as `\\?\` in a
+    /// Windows path - that is part of, and precedes the rest of, the root.
+    fn prefix_length<U : CodeUnit>(path : &[U]) -> usize {
+        let _ = path;
+
+        0
+    }
+
+    /// Indicates whether the root of a path of the given classification is
+    /// the home designator, rather than a prefix or a separator.
+    fn is_home_rooted(classification : Self::Classification) -> bool {
+        let _ = classification;
+
+        false
+    }
+
+    /// Evaluates whether a name - i.e. a directory part or an entry name -
+    /// that contains no invalid characters nonetheless cannot be used
+    /// as-is, such as the device name `"CON"` on Windows.
+    fn name_is_reserved(name : &str) -> bool {
+        let _ = name;
+
+        false
+    }
+
+    /// Obtains the character to which `c` is mapped when comparing paths,
+    /// such that two characters that are considered equal in a name map
+    /// to the same character.
+    ///
+    /// The default implementation returns `c` if the syntax is
+    /// [case-sensitive](Self::IS_CASE_SENSITIVE), and its ASCII upper-case
+    /// otherwise.
+    fn char_fold_case(c : char) -> char {
+        if Self::IS_CASE_SENSITIVE {
+            c
+        } else {
+            c.to_ascii_uppercase()
+        }
+    }
+}
+
+
+/// Classifies `path` according to the syntax `S`.
+///
+/// # Parameters:
+/// - `path` - the given path to be classified;
+/// - `parse_flags` - flags that moderate the classification;
+pub fn path_classify<S : PathSyntax>(
+    path : &str,
+    parse_flags : i32,
+) -> (
+    S::Classification,    // classification
+    ClassificationResult, // classification_result
+) {
+    // All characters of significance to the classification are ASCII, and
+    // so never occur within a multi-byte UTF-8 sequence, so the byte-wise
+    // results are on `char` boundaries
+
+    path_classify_units::<S, u8>(path.as_bytes(), parse_flags)
+}
+
+/// Classifies `path`, expressed as a sequence of code units, according to
+/// the syntax `S`.
+///
+/// The positions in the result are in units of `U`.
+///
+/// # Parameters:
+/// - `path` - the given path to be classified;
+/// - `parse_flags` - flags that moderate the classification;
+pub fn path_classify_units<S : PathSyntax, U : CodeUnit>(
+    path : &[U],
+    parse_flags : i32,
+) -> (
+    S::Classification,    // classification
+    ClassificationResult, // classification_result
+) {
+    if path.is_empty() {
+        return (
+            // argument list:
+            S::EMPTY,
+            ClassificationResult::empty(),
+        );
+    }
+
+    let mut cr = ClassificationResult::empty();
+
+    cr.Input = PoSl::new(0, path.len());
+
+    let (mut cl, root, path_root_stripped) = S::classify_root(path, parse_flags);
+
+    cr.Prefix = PoSl::new(0, S::prefix_length(path));
+    cr.Root = PoSl::new(0, root.len());
+
+    // now search within root-stripped path
+
+    let last_slash = S::find_last_separator(path_root_stripped.subslice_of(path));
+
+    match last_slash {
+        Some(index) => {
+            // if there's a slash, then there is a directory and, potentially, an entry
+
+            let dir_len = index + 1;
+
+            cr.Directory = PoSl::new(root.len(), dir_len);
+
+            let (num_parts, num_dir_parts) =
+                count_directory_parts_::<S, U>(cr.Directory.subslice_of(path), parse_flags);
+
+            cr.NumDirectoryParts = num_parts;
+            cr.NumDotsDirectoryParts = num_dir_parts;
+
+            cr.EntryName = PoSl::new(root.len() + dir_len, path_root_stripped.len() - dir_len);
+        },
+        None => {
+            cr.Directory = PoSl::new(root.len(), 0);
+
+            // if there's no slash, then the whole (stripped) path is the entry
+
+            cr.EntryName = PoSl::new(root.len(), path_root_stripped.len());
+        },
+    }
+
+    if cr.EntryName.is_empty() {
+        cr.Stem = cr.EntryName;
+        cr.Extension = cr.EntryName;
+    } else {
+        let entry_name = cr.EntryName.subslice_of(path);
+        let last_entry_dot = entry_name.iter().rposition(|&u| '.' == u.as_char());
+
+        match last_entry_dot {
+            Some(index) => {
+                // handle special dots directories "." and ".."
+
+                let mut is_dots = false;
+
+                if !is_dots && 1 == cr.EntryName.len() {
+                    is_dots = true;
+                }
+
+                if !is_dots && 2 == cr.EntryName.len() && entry_name.iter().all(|&u| '.' == u.as_char()) {
+                    is_dots = true;
+                }
+
+                if is_dots {
+                    cr.Stem = cr.EntryName;
+                    cr.Extension = PoSl::new(cr.EntryName.offset + cr.EntryName.len(), 0);
+                } else {
+                    cr.Stem = PoSl::new(cr.EntryName.offset, index);
+                    cr.Extension = PoSl::new(cr.EntryName.offset + index, cr.EntryName.len() - index);
+                }
+            },
+            None => {
+                cr.Stem = cr.EntryName;
+                cr.Extension = PoSl::new(cr.EntryName.offset + cr.EntryName.len(), 0);
+            },
+        }
+    }
+
+    cr.Location = PoSl::new(0, cr.EntryName.offset);
+
+    if let Some(index) = find_first_invalid_::<S, U>(path_root_stripped.subslice_of(path)) {
+        cr.FirstInvalid = PoSl::new(root.len() + index, 1);
+
+        if !S::ignores_invalid_chars(path, parse_flags) {
+            cl = S::INVALID_CHARS;
+        }
+    }
+
+    (cl, cr)
+}
+
+/// Looks for the first invalid character in the slice.
+fn find_first_invalid_<S : PathSyntax, U : CodeUnit>(s : &[U]) -> Option<usize> {
+    s.iter().position(|&u| {
+        let c = u.as_char();
+
+        char::REPLACEMENT_CHARACTER != c && S::char_is_invalid(c)
+    })
+}
+
+fn count_directory_parts_<S : PathSyntax, U : CodeUnit>(
+    s : &[U],
+    parse_flags : i32,
+) -> (
+    usize, // number_of_parts
+    usize, // number_of_dots_parts
+) {
+    {
+        let _ = parse_flags;
+    }
+
+    // This function counts the number of directory parts and the number of
+    // those that are dots directories
+
+    let mut number_of_parts = 0usize;
+    let mut number_of_dots_parts = 0usize;
+
+    let mut prev = 'X';
+
+    let mut num_dots = 0;
+
+    for &u in s {
+        let c = u.as_char();
+
+        if S::char_is_path_name_separator(c) {
+            match num_dots {
+                1 | 2 => number_of_dots_parts += 1,
+                _ => (),
+            }
+
+            if S::char_is_path_name_separator(prev) {
+            } else {
+                number_of_parts += 1;
+            }
+
+            num_dots = 0;
+        } else {
+            if '.' == c {
+                num_dots += 1;
+            } else {
+                num_dots += 100;
+            }
+        }
+
+        prev = c;
+    }
+
+    (number_of_parts, number_of_dots_parts)
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate::libpath::util::{
+        unix::UnixSyntax,
+        windows::WindowsSyntax,
+    };
+
+
+    /// A syntax in which `':'` is the separator, `'@'` denotes a root, and
+    /// `'/'` is invalid (as in the classic Mac OS, more or less).
+    #[derive(Clone, Copy)]
+    #[derive(Debug)]
+    #[derive(PartialEq, Eq)]
+    struct ColonSyntax;
+
+    #[derive(Clone, Copy)]
+    #[derive(Debug)]
+    #[derive(PartialEq, Eq, PartialOrd, Ord)]
+    #[derive(Hash)]
+    enum ColonClassification {
+        InvalidChars,
+        Empty,
+        Relative,
+        VolumeRooted,
+    }
+
+    impl PathSyntax for ColonSyntax {
+        type Classification = ColonClassification;
+
+        const EMPTY : Self::Classification = ColonClassification::Empty;
+        const INVALID_CHARS : Self::Classification = ColonClassification::InvalidChars;
+        const IS_CASE_SENSITIVE : bool = false;
+        const PREFERRED_SEPARATOR : char = ':';
+        const RELATIVE : Self::Classification = ColonClassification::Relative;
+
+        fn char_is_path_name_separator(c : char) -> bool {
+            ':' == c
+        }
+
+        fn char_is_invalid(c : char) -> bool {
+            '/' == c
+        }
+
+        fn classify_root<U : CodeUnit>(
+            path : &[U],
+            parse_flags : i32,
+        ) -> (Self::Classification, PoSl, PoSl) {
+            let _ = parse_flags;
+
+            match path.iter().position(|&u| '@' == u.as_char()) {
+                Some(index) => {
+                    (
+                        // argument list:
+                        ColonClassification::VolumeRooted,
+                        PoSl::new(0, index + 1),
+                        PoSl::new(index + 1, path.len() - (index + 1)),
+                    )
+                },
+                None => {
+                    (
+                        // argument list:
+                        ColonClassification::Relative,
+                        PoSl::empty(),
+                        PoSl::new(0, path.len()),
+                    )
+                },
+            }
+        }
+    }
+
+
+    #[test]
+    fn TEST_count_directory_parts__1() {
+        assert_eq!((0, 0), count_directory_parts_::<UnixSyntax, u8>(b"", 0));
+        assert_eq!((1, 0), count_directory_parts_::<UnixSyntax, u8>(b"/", 0));
+        assert_eq!((1, 0), count_directory_parts_::<UnixSyntax, u8>(b"abc/", 0));
+        assert_eq!((3, 0), count_directory_parts_::<UnixSyntax, u8>(b"/abc/def/", 0));
+        assert_eq!((2, 2), count_directory_parts_::<UnixSyntax, u8>(b"./../", 0));
+        assert_eq!((3, 1), count_directory_parts_::<UnixSyntax, u8>(b"abc/../def/", 0));
+        assert_eq!((1, 0), count_directory_parts_::<UnixSyntax, u8>(b".../", 0));
+        assert_eq!((1, 0), count_directory_parts_::<UnixSyntax, u8>(b"abc\\def/", 0));
+
+        assert_eq!((0, 0), count_directory_parts_::<WindowsSyntax, u8>(b"", 0));
+        assert_eq!((1, 0), count_directory_parts_::<WindowsSyntax, u8>(b"\\", 0));
+        assert_eq!((1, 0), count_directory_parts_::<WindowsSyntax, u8>(b"abc/", 0));
+        assert_eq!((3, 0), count_directory_parts_::<WindowsSyntax, u8>(b"\\abc/def\\", 0));
+        assert_eq!((2, 2), count_directory_parts_::<WindowsSyntax, u8>(b".\\..\\", 0));
+        assert_eq!((3, 1), count_directory_parts_::<WindowsSyntax, u8>(b"abc/../def/", 0));
+    }
+
+    #[test]
+    fn TEST_find_last_separator__1() {
+        assert_eq!(None, UnixSyntax::find_last_separator::<u8>(b""));
+        assert_eq!(None, UnixSyntax::find_last_separator::<u8>(b"abc"));
+        assert_eq!(None, UnixSyntax::find_last_separator::<u8>(b"abc\\def"));
+        assert_eq!(Some(0), UnixSyntax::find_last_separator::<u8>(b"/abc"));
+        assert_eq!(Some(3), UnixSyntax::find_last_separator::<u8>(b"abc/def"));
+        assert_eq!(Some(7), UnixSyntax::find_last_separator::<u8>(b"abc/def/"));
+
+        assert_eq!(None, WindowsSyntax::find_last_separator::<u8>(b""));
+        assert_eq!(None, WindowsSyntax::find_last_separator::<u8>(b"abc"));
+        assert_eq!(Some(0), WindowsSyntax::find_last_separator::<u8>(b"/abc"));
+        assert_eq!(Some(0), WindowsSyntax::find_last_separator::<u8>(b"\\abc"));
+        assert_eq!(Some(3), WindowsSyntax::find_last_separator::<u8>(b"abc\\def"));
+        assert_eq!(Some(7), WindowsSyntax::find_last_separator::<u8>(b"abc/def\\"));
+        assert_eq!(Some(7), WindowsSyntax::find_last_separator::<u8>(b"abc/def\\ghi"));
+        assert_eq!(Some(7), WindowsSyntax::find_last_separator::<u8>(b"a/bcdef\\g"));
+        assert_eq!(Some(7), WindowsSyntax::find_last_separator::<u8>(b"abc\\def/"));
+    }
+
+    #[test]
+    fn TEST_path_classify_WITH_DOTS_ENTRY_NAME() {
+        // the (empty) extension of a "." or ".." entry name is at the end
+        // of the entry name, rather than at an offset within the path that
+        // may not even be on a character boundary
+
+        for (path, entry_name_offset, entry_name_len) in [
+            (".", 0, 1),
+            ("..", 0, 2),
+            ("dir/.", 4, 1),
+            ("dir/..", 4, 2),
+            ("caf\u{e9}/.", 6, 1),
+            ("\u{e9}/..", 3, 2),
+        ] {
+            let expected_extension = PoSl::new(entry_name_offset + entry_name_len, 0);
+
+            let (_, cr) = path_classify::<UnixSyntax>(path, 0);
+
+            assert_eq!(
+                PoSl::new(entry_name_offset, entry_name_len),
+                cr.EntryName,
+                "path='{path}'"
+            );
+            assert_eq!(cr.EntryName, cr.Stem, "path='{path}'");
+            assert_eq!(expected_extension, cr.Extension, "path='{path}'");
+            assert_eq!("", cr.Extension.substring_of(path), "path='{path}'");
+            assert!(cr.is_consistent_with(path), "path='{path}'");
+
+            let (_, cr) = path_classify_fast::<WindowsSyntax>(path, 0);
+
+            assert_eq!(expected_extension, cr.Extension, "path='{path}'");
+            assert!(cr.is_consistent_with(path), "path='{path}'");
+        }
+    }
+
+    #[test]
+    fn TEST_path_classify_WITH_CUSTOM_SYNTAX() {
+        {
+            let path = "Macintosh HD@System:Library:file.ext";
+            let (cl, cr) = path_classify::<ColonSyntax>(path, 0);
+
+            assert_eq!(ColonClassification::VolumeRooted, cl);
+
+            assert_eq!("Macintosh HD@", cr.Root.substring_of(path));
+            assert_eq!("System:Library:", cr.Directory.substring_of(path));
+            assert_eq!(2, cr.NumDirectoryParts);
+            assert_eq!("file.ext", cr.EntryName.substring_of(path));
+            assert_eq!("file", cr.Stem.substring_of(path));
+            assert_eq!(".ext", cr.Extension.substring_of(path));
+            assert!(cr.FirstInvalid.is_empty());
+        }
+
+        {
+            let path = "dir:a/b";
+            let (cl, cr) = path_classify::<ColonSyntax>(path, 0);
+
+            assert_eq!(ColonClassification::InvalidChars, cl);
+
+            assert_eq!(PoSl::new(5, 1), cr.FirstInvalid);
+            assert_eq!("a/b", cr.EntryName.substring_of(path));
+
+            let (cl, _) = path_classify::<ColonSyntax>(path, classification_flags::IGNORE_INVALID_CHARS);
+
+            assert_eq!(ColonClassification::Relative, cl);
+        }
+
+        {
+            let (cl, cr) = path_classify::<ColonSyntax>("", 0);
+
+            assert_eq!(ColonClassification::Empty, cl);
+            assert_eq!(ClassificationResult::empty(), cr);
+        }
+    }
+
+    #[test]
+    fn TEST_path_classify_units_WITH_CUSTOM_SYNTAX() {
+        let path : &[u16] = &[0x61, 0x40, 0xD800, 0x3A, 0x62, 0x2E, 0x63];
+        let (cl, cr) = path_classify_units::<ColonSyntax, u16>(path, 0);
+
+        assert_eq!(ColonClassification::VolumeRooted, cl);
+
+        assert_eq!(PoSl::new(0, 2), cr.Root);
+        assert_eq!(PoSl::new(2, 2), cr.Directory);
+        assert_eq!(PoSl::new(4, 3), cr.EntryName);
+        assert_eq!(PoSl::new(5, 2), cr.Extension);
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -0,0 +1,599 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/syntax/classified_path.rs
+ *
+ * Purpose: Definition of ClassifiedPath, and its interoperation with
+ *          std::path.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+use super::{
+    path_classify,
+    PathSyntax,
+};
+#[cfg(feature = "std")]
+use crate::libpath::util::common::PathNotUnicodeError;
+use crate::libpath::util::common::{
+    ClassificationResult,
+    Components,
+    ExpandedClassificationResult,
+};
+
+use alloc::{
+    borrow::Cow,
+    string::String,
+};
+use core::{
+    fmt,
+    marker::PhantomData,
+};
+#[cfg(feature = "std")]
+use std::path::{
+    Path,
+    PathBuf,
+};
+
+
+/// A path together with its classification according to the syntax `S`.
+///
+/// # Note:
+/// [`unix::ClassifiedPath`](crate::libpath::util::unix::ClassifiedPath) and
+/// [`windows::ClassifiedPath`](crate::libpath::util::windows::ClassifiedPath)
+/// are aliases of this for their respective syntaxes.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "ClassifiedPathRepr<S>",
+        bound(
+            serialize = "S::Classification : serde::Serialize",
+            deserialize = "S::Classification : serde::Deserialize<'de>"
+        )
+    )
+)]
+pub struct ClassifiedPath<'a, S : PathSyntax> {
+    path :           Cow<'a, str>,
+    classification : S::Classification,
+    result :         ClassificationResult,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    syntax :         PhantomData<fn() -> S>,
+}
+
+// API functions
+impl<'a, S : PathSyntax> ClassifiedPath<'a, S> {
+    /// Classifies `path`, borrowing it.
+    pub fn new(
+        path : &'a str,
+        parse_flags : i32,
+    ) -> Self {
+        let (classification, result) = path_classify::<S>(path, parse_flags);
+
+        Self {
+            path : Cow::Borrowed(path),
+            classification,
+            result,
+            syntax : PhantomData,
+        }
+    }
+
+    /// Classifies `path`, taking ownership of it.
+    pub fn from_string(
+        path : String,
+        parse_flags : i32,
+    ) -> ClassifiedPath<'static, S> {
+        let (classification, result) = path_classify::<S>(&path, parse_flags);
+
+        ClassifiedPath {
+            path : Cow::Owned(path),
+            classification,
+            result,
+            syntax : PhantomData,
+        }
+    }
+}
+
+// Non-mutating methods
+impl<S : PathSyntax> ClassifiedPath<'_, S> {
+    /// The full path.
+    pub fn as_str(&self) -> &str {
+        &self.path
+    }
+
+    /// The path's classification.
+    pub fn classification(&self) -> S::Classification {
+        self.classification
+    }
+
+    /// The path's classification result.
+    pub fn result(&self) -> &ClassificationResult {
+        &self.result
+    }
+
+    /// The root part of the path, e.g. `"~"` or `"C:"`.
+    pub fn root(&self) -> &str {
+        self.result.Root.substring_of(&self.path)
+    }
+
+    /// The location (root and directory) part of the path.
+    pub fn location(&self) -> &str {
+        self.result.Location.substring_of(&self.path)
+    }
+
+    /// The directory part of the path.
+    pub fn directory(&self) -> &str {
+        self.result.Directory.substring_of(&self.path)
+    }
+
+    /// The entry name part of the path.
+    pub fn entry_name(&self) -> &str {
+        self.result.EntryName.substring_of(&self.path)
+    }
+
+    /// The stem of the entry name.
+    pub fn stem(&self) -> &str {
+        self.result.Stem.substring_of(&self.path)
+    }
+
+    /// The extension of the entry name, including the leading `'.'`.
+    pub fn extension(&self) -> &str {
+        self.result.Extension.substring_of(&self.path)
+    }
+
+    /// The classification result in its expanded form, i.e. with the
+    /// text of each element.
+    pub fn expanded(&self) -> ExpandedClassificationResult<'_> {
+        ExpandedClassificationResult::new(&self.path, &self.result)
+    }
+
+    /// An iterator over the path's components.
+    pub fn components(&self) -> Components<'_> {
+        Components::new(
+            &self.path,
+            &self.result,
+            S::is_home_rooted(self.classification),
+            S::char_is_path_name_separator,
+        )
+    }
+
+    /// The path as a `std::path::Path`.
+    #[cfg(feature = "std")]
+    pub fn as_path(&self) -> &Path {
+        Path::new(self.path.as_ref())
+    }
+
+    /// Obtains an instance that owns its path.
+    pub fn into_owned(self) -> ClassifiedPath<'static, S> {
+        ClassifiedPath {
+            path :           Cow::Owned(self.path.into_owned()),
+            classification : self.classification,
+            result :         self.result,
+            syntax :         PhantomData,
+        }
+    }
+}
+
+// Trait implementations
+
+impl<S : PathSyntax> Clone for ClassifiedPath<'_, S> {
+    fn clone(&self) -> Self {
+        Self {
+            path :           self.path.clone(),
+            classification : self.classification,
+            result :         self.result,
+            syntax :         PhantomData,
+        }
+    }
+}
+
+impl<S : PathSyntax> fmt::Debug for ClassifiedPath<'_, S> {
+    fn fmt(
+        &self,
+        f : &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("ClassifiedPath")
+            .field("path", &self.path)
+            .field("classification", &self.classification)
+            .field("result", &self.result)
+            .finish()
+    }
+}
+
+impl<S : PathSyntax> PartialEq for ClassifiedPath<'_, S> {
+    fn eq(
+        &self,
+        other : &Self,
+    ) -> bool {
+        self.path == other.path && self.classification == other.classification && self.result == other.result
+    }
+}
+
+impl<S : PathSyntax> Eq for ClassifiedPath<'_, S> {
+}
+
+impl<S : PathSyntax> AsRef<str> for ClassifiedPath<'_, S> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S : PathSyntax> AsRef<Path> for ClassifiedPath<'_, S> {
+    fn as_ref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, S : PathSyntax> TryFrom<&'a Path> for ClassifiedPath<'a, S> {
+    type Error = PathNotUnicodeError;
+
+    fn try_from(path : &'a Path) -> Result<Self, Self::Error> {
+        match path.to_str() {
+            Some(s) => Ok(Self::new(s, 0)),
+            None => Err(PathNotUnicodeError),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S : PathSyntax> TryFrom<PathBuf> for ClassifiedPath<'static, S> {
+    type Error = PathNotUnicodeError;
+
+    fn try_from(path : PathBuf) -> Result<Self, Self::Error> {
+        match path.into_os_string().into_string() {
+            Ok(s) => Ok(Self::from_string(s, 0)),
+            Err(_) => Err(PathNotUnicodeError),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<S : PathSyntax> From<ClassifiedPath<'_, S>> for PathBuf {
+    fn from(path : ClassifiedPath<'_, S>) -> Self {
+        PathBuf::from(path.path.into_owned())
+    }
+}
+
+
+/// Deserialisation form of [`ClassifiedPath`], which is checked for
+/// consistency before conversion.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(bound(deserialize = "S::Classification : serde::Deserialize<'de>"))]
+struct ClassifiedPathRepr<S : PathSyntax> {
+    path :           String,
+    classification : S::Classification,
+    result :         ClassificationResult,
+}
+
+#[cfg(feature = "serde")]
+impl<S : PathSyntax> TryFrom<ClassifiedPathRepr<S>> for ClassifiedPath<'_, S> {
+    type Error = &'static str;
+
+    fn try_from(repr : ClassifiedPathRepr<S>) -> Result<Self, Self::Error> {
+        if !repr.result.is_consistent_with(&repr.path) {
+            return Err("classification result is not consistent with path");
+        }
+
+        Ok(Self {
+            path :           Cow::Owned(repr.path),
+            classification : repr.classification,
+            result :         repr.result,
+            syntax :         PhantomData,
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    mod unix {
+        #![allow(non_snake_case)]
+
+        use crate::libpath::util::{
+            common::Component,
+            unix::{
+                Classification,
+                ClassifiedPath,
+            },
+        };
+        #[cfg(feature = "std")]
+        use std::path::{
+            Path,
+            PathBuf,
+        };
+
+
+        #[test]
+        fn TEST_ClassifiedPath_ACCESSORS() {
+            let cp = ClassifiedPath::new("~/dir/sub-dir/file.ext", 0);
+
+            assert_eq!(Classification::HomeRooted, cp.classification());
+            assert_eq!("~/dir/sub-dir/file.ext", cp.as_str());
+            assert_eq!("~", cp.root());
+            assert_eq!("~/dir/sub-dir/", cp.location());
+            assert_eq!("/dir/sub-dir/", cp.directory());
+            assert_eq!("file.ext", cp.entry_name());
+            assert_eq!("file", cp.stem());
+            assert_eq!(".ext", cp.extension());
+        }
+
+        #[test]
+        fn TEST_ClassifiedPath_components() {
+            {
+                let cp = ClassifiedPath::new("/dir//sub-dir/./../file.ext", 0);
+
+                assert_eq!(
+                    vec![
+                        Component::RootDir,
+                        Component::Normal("dir"),
+                        Component::Normal("sub-dir"),
+                        Component::CurDir,
+                        Component::ParentDir,
+                        Component::Normal("file.ext"),
+                    ],
+                    cp.components().collect::<Vec<_>>()
+                );
+            }
+
+            {
+                let cp = ClassifiedPath::new("~/dir/", 0);
+
+                assert_eq!(
+                    vec![Component::Home("~"), Component::Normal("dir")],
+                    cp.components().collect::<Vec<_>>()
+                );
+            }
+
+            {
+                let cp = ClassifiedPath::new("", 0);
+
+                assert_eq!(0, cp.components().count());
+            }
+        }
+
+        #[cfg(feature = "std")]
+        #[test]
+        fn TEST_ClassifiedPath_components_MATCH_std_path() {
+            for path in ["/dir/sub-dir/file.ext", "dir/file", "../dir/file", "/"] {
+                let cp = ClassifiedPath::new(path, 0);
+
+                let ours : Vec<std::path::Component> = cp.components().map(Into::into).collect();
+                let stds : Vec<std::path::Component> = Path::new(path).components().collect();
+
+                assert_eq!(stds, ours, "path='{path}'");
+            }
+        }
+
+        #[cfg(feature = "std")]
+        #[test]
+        fn TEST_ClassifiedPath_components_ROUNDTRIP_WITH_FOREIGN_DRIVES() {
+            use crate::libpath::util::unix::classification_flags::*;
+
+            let cases = [
+                (RECOGNISE_MSYS_DRIVES, "/c"),
+                (RECOGNISE_WSL_DRIVES, "/mnt/c"),
+                (RECOGNISE_CYGWIN_DRIVES, "/cygdrive/c"),
+            ];
+
+            for (parse_flags, root) in cases {
+                for suffix in ["", "/", "/Users/me/file.ext", "/Users//me/./../file.ext"] {
+                    let path = format!("{root}{suffix}");
+                    let cp = ClassifiedPath::new(&path, parse_flags);
+
+                    assert_eq!(Classification::ForeignDriveRooted, cp.classification(), "path='{path}'");
+                    assert_eq!(Some(Component::Prefix(root)), cp.components().next(), "path='{path}'");
+
+                    let pb : PathBuf = cp.components().into_std().collect();
+
+                    assert_eq!(Path::new(&path), pb, "path='{path}'");
+                }
+            }
+        }
+
+        #[cfg(feature = "std")]
+        #[test]
+        fn TEST_ClassifiedPath_FROM_AND_INTO_PathBuf() {
+            let pb = PathBuf::from("/dir/file.ext");
+
+            let cp = ClassifiedPath::try_from(pb.as_path()).unwrap();
+
+            assert_eq!(Classification::SlashRooted, cp.classification());
+            assert_eq!("file.ext", cp.entry_name());
+
+            let cp = ClassifiedPath::try_from(pb.clone()).unwrap();
+
+            assert_eq!(".ext", cp.extension());
+            assert_eq!(pb, PathBuf::from(cp));
+        }
+
+        #[cfg(all(unix, feature = "std"))]
+        #[test]
+        fn TEST_ClassifiedPath_FROM_NON_UNICODE_Path() {
+            use crate::libpath::util::common::PathNotUnicodeError;
+            use std::{
+                ffi::OsStr,
+                os::unix::ffi::OsStrExt,
+            };
+
+            let path = Path::new(OsStr::from_bytes(b"/dir/\xFF.ext"));
+
+            assert_eq!(Err(PathNotUnicodeError), ClassifiedPath::try_from(path));
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn TEST_ClassifiedPath_serde_ROUNDTRIP() {
+            let cp = ClassifiedPath::new(r"/dir/file.ext", 0);
+
+            let json = serde_json::to_string(&cp).unwrap();
+
+            assert!(json.contains(r#""classification":"SlashRooted""#), "json={json}");
+
+            let cp2 : ClassifiedPath = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(cp, cp2);
+            assert_eq!("file.ext", cp2.entry_name());
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn TEST_ClassifiedPath_serde_REJECTS_INCONSISTENT_RESULT() {
+            let cp = ClassifiedPath::new(r"/dir/file.ext", 0);
+
+            let json = serde_json::to_string(&cp).unwrap().replace(r"/dir/file.ext", "x");
+
+            assert!(serde_json::from_str::<ClassifiedPath>(&json).is_err());
+        }
+    }
+
+    mod windows {
+        #![allow(non_snake_case)]
+
+        use crate::libpath::util::{
+            common::Component,
+            windows::{
+                Classification,
+                ClassifiedPath,
+            },
+        };
+        #[cfg(feature = "std")]
+        use std::path::PathBuf;
+
+
+        #[test]
+        fn TEST_ClassifiedPath_ACCESSORS() {
+            let cp = ClassifiedPath::new(r"C:\dir\sub-dir\file.ext", 0);
+
+            assert_eq!(Classification::DriveLetterRooted, cp.classification());
+            assert_eq!(r"C:\dir\sub-dir\file.ext", cp.as_str());
+            assert_eq!("C:", cp.root());
+            assert_eq!(r"C:\dir\sub-dir\", cp.location());
+            assert_eq!(r"\dir\sub-dir\", cp.directory());
+            assert_eq!("file.ext", cp.entry_name());
+            assert_eq!("file", cp.stem());
+            assert_eq!(".ext", cp.extension());
+        }
+
+        #[test]
+        fn TEST_ClassifiedPath_components() {
+            {
+                let cp = ClassifiedPath::new(r"C:\dir/sub-dir\.\..\file.ext", 0);
+
+                assert_eq!(
+                    vec![
+                        Component::Prefix("C:"),
+                        Component::RootDir,
+                        Component::Normal("dir"),
+                        Component::Normal("sub-dir"),
+                        Component::CurDir,
+                        Component::ParentDir,
+                        Component::Normal("file.ext"),
+                    ],
+                    cp.components().collect::<Vec<_>>()
+                );
+            }
+
+            {
+                let cp = ClassifiedPath::new(r"C:dir\file.ext", 0);
+
+                assert_eq!(
+                    vec![
+                        Component::Prefix("C:"),
+                        Component::Normal("dir"),
+                        Component::Normal("file.ext"),
+                    ],
+                    cp.components().collect::<Vec<_>>()
+                );
+            }
+
+            {
+                let cp = ClassifiedPath::new(r"\dir\", 0);
+
+                assert_eq!(
+                    vec![Component::RootDir, Component::Normal("dir")],
+                    cp.components().collect::<Vec<_>>()
+                );
+            }
+        }
+
+        #[cfg(feature = "std")]
+        #[test]
+        fn TEST_ClassifiedPath_FROM_AND_INTO_PathBuf() {
+            let pb = PathBuf::from(r"C:\dir\file.ext");
+
+            let cp = ClassifiedPath::try_from(pb.as_path()).unwrap();
+
+            assert_eq!(Classification::DriveLetterRooted, cp.classification());
+            assert_eq!("file.ext", cp.entry_name());
+
+            let cp = ClassifiedPath::try_from(pb.clone()).unwrap();
+
+            assert_eq!(".ext", cp.extension());
+            assert_eq!(pb, PathBuf::from(cp));
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn TEST_ClassifiedPath_serde_ROUNDTRIP() {
+            let cp = ClassifiedPath::new(r"C:\dir\file.ext", 0);
+
+            let json = serde_json::to_string(&cp).unwrap();
+
+            assert!(json.contains(r#""classification":"DriveLetterRooted""#), "json={json}");
+
+            let cp2 : ClassifiedPath = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(cp, cp2);
+            assert_eq!("file.ext", cp2.entry_name());
+        }
+
+        #[cfg(feature = "serde")]
+        #[test]
+        fn TEST_ClassifiedPath_serde_REJECTS_INCONSISTENT_RESULT() {
+            let cp = ClassifiedPath::new(r"C:\dir\file.ext", 0);
+
+            let json = serde_json::to_string(&cp).unwrap().replace(r"C:\\dir\\file.ext", "x");
+
+            assert!(serde_json::from_str::<ClassifiedPath>(&json).is_err());
+        }
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
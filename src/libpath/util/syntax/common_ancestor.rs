@@ -0,0 +1,212 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/syntax/common_ancestor.rs
+ *
+ * Purpose: Obtaining the longest common ancestor of a set of paths.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+use super::{
+    classification_flags,
+    path_classify,
+    PathSyntax,
+};
+
+
+/// Obtains the longest common ancestor directory of `paths`, according to
+/// the syntax `S`.
+///
+/// The paths are compared component-wise - using the `Root` and
+/// `Directory` of their classification, and so disregarding their entry
+/// names - so that `"/abc/x"` and `"/abd/y"` have the common ancestor
+/// `"/"`, not `"/ab"`. Roots and names are compared according to the case
+/// rules of the syntax, and all separators are equivalent.
+///
+/// # Parameters:
+/// - `paths` - the paths;
+///
+/// # Returns:
+/// `None` if `paths` is empty or if the roots of any two paths differ;
+/// otherwise the common ancestor as a slice of the first path. This has
+/// no trailing separator unless it is a bare root, such as `"/"` or
+/// `"C:\"`, and is empty if the paths are relative and have no directory
+/// in common.
+///
+/// # Note:
+/// The comparison is lexical, and so `"."` and `".."` parts are compared
+/// as names. Apply [`normalise()`](super::normalise()) first if they may
+/// be present.
+pub fn common_ancestor<'a, S, I>(paths : I) -> Option<&'a str>
+where
+    S : PathSyntax,
+    I : IntoIterator<Item = &'a str>,
+{
+    let mut paths = paths.into_iter();
+
+    let first = paths.next()?;
+    let (first_root, first_has_root_dir, first_directory) = split_::<S>(first);
+
+    let mut num_common = directory_parts_::<S>(first_directory).count();
+
+    for path in paths {
+        let (root, has_root_dir, directory) = split_::<S>(path);
+
+        if has_root_dir != first_has_root_dir || !names_equal_::<S>(root, first_root) {
+            return None;
+        }
+
+        num_common = directory_parts_::<S>(first_directory)
+            .zip(directory_parts_::<S>(directory))
+            .take(num_common)
+            .take_while(|&(lhs, rhs)| names_equal_::<S>(lhs, rhs))
+            .count();
+    }
+
+    let last_common = num_common
+        .checked_sub(1)
+        .and_then(|n| directory_parts_::<S>(first_directory).nth(n));
+
+    let end = match last_common {
+        Some(part) => part.as_ptr() as usize - first.as_ptr() as usize + part.len(),
+        None => first_directory.as_ptr() as usize - first.as_ptr() as usize,
+    };
+
+    Some(&first[..end])
+}
+
+
+/// Splits `path` into its root, whether that is followed by a separator,
+/// and its directory (less that separator).
+fn split_<S : PathSyntax>(
+    path : &str
+) -> (
+    &str, // root
+    bool, // has_root_dir
+    &str, // directory
+) {
+    let (_, cr) = path_classify::<S>(path, classification_flags::IGNORE_INVALID_CHARS);
+
+    let root = cr.Root.substring_of(path);
+    let directory = cr.Directory.substring_of(path);
+
+    match directory.chars().next() {
+        Some(c) if S::char_is_path_name_separator(c) => (root, true, &directory[c.len_utf8()..]),
+        _ => (root, false, directory),
+    }
+}
+
+fn directory_parts_<S : PathSyntax>(directory : &str) -> impl Iterator<Item = &str> {
+    directory
+        .split(S::char_is_path_name_separator)
+        .filter(|part| !part.is_empty())
+}
+
+fn names_equal_<S : PathSyntax>(
+    lhs : &str,
+    rhs : &str,
+) -> bool {
+    let map = |c : char| {
+        if S::char_is_path_name_separator(c) {
+            S::PREFERRED_SEPARATOR
+        } else {
+            S::char_fold_case(c)
+        }
+    };
+
+    lhs.chars().map(map).eq(rhs.chars().map(map))
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate::libpath::util::{
+        unix::UnixSyntax,
+        windows::WindowsSyntax,
+    };
+
+
+    #[test]
+    fn TEST_common_ancestor_UNIX() {
+        for (paths, expected) in [
+            (&[][..], None),
+            (&["/abc/x", "/abd/y"][..], Some("/")),
+            (&["/a/b/c.txt", "/a/b/d/e.txt"][..], Some("/a/b")),
+            (&["/a/b/c.txt", "/a/b/d/e.txt", "/a/f.txt"][..], Some("/a")),
+            (&["/a//b/c.txt", "/a/b/"][..], Some("/a//b")),
+            (&["/a/b/c.txt"][..], Some("/a/b")),
+            (&["/c.txt"][..], Some("/")),
+            (&["src/a.rs", "src/b/c.rs"][..], Some("src")),
+            (&["src/a.rs", "docs/b.md"][..], Some("")),
+            (&["a.rs", "b.rs"][..], Some("")),
+            (&["/a/b/c", "a/b/c"][..], None),
+            (&["/A/b", "/a/b"][..], Some("/")),
+        ] {
+            assert_eq!(
+                expected,
+                common_ancestor::<UnixSyntax, _>(paths.iter().copied()),
+                "paths={paths:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_common_ancestor_WINDOWS() {
+        for (paths, expected) in [
+            (&[r"C:\abc\x", r"C:\abd\y"][..], Some(r"C:\")),
+            (&[r"C:\Dir\Sub\a.txt", "c:/dir/SUB/b.txt"][..], Some(r"C:\Dir\Sub")),
+            (&[r"C:\dir\a.txt", r"D:\dir\b.txt"][..], None),
+            (&[r"C:\dir\a.txt", r"C:dir\b.txt"][..], None),
+            (
+                &[r"\\server\share\a\b.txt", r"\\SERVER\Share\a\c\d.txt"][..],
+                Some(r"\\server\share\a"),
+            ),
+            (&[r"\\server\share\a\b.txt", r"\\server\other\a\b.txt"][..], None),
+            (&[r"src\a.rs", "src/b/c.rs"][..], Some("src")),
+        ] {
+            assert_eq!(
+                expected,
+                common_ancestor::<WindowsSyntax, _>(paths.iter().copied()),
+                "paths={paths:?}"
+            );
+        }
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
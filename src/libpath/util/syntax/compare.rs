@@ -0,0 +1,454 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/syntax/compare.rs
+ *
+ * Purpose: Comparison of paths according to the rules of a syntax.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+use super::{
+    comparison_flags,
+    normalise,
+    path_classify,
+    PathSyntax,
+};
+
+use alloc::string::String;
+use core::{
+    cmp::Ordering,
+    fmt,
+    hash::{
+        Hash,
+        Hasher,
+    },
+    marker::PhantomData,
+};
+
+
+/// A key that identifies a path according to the rules of the syntax `S`,
+/// such that two paths have equal keys if and only if they are equal
+/// according to [`paths_equal()`], and that keys are ordered as by
+/// [`path_cmp()`].
+///
+/// The key may be used in a `HashMap` or `BTreeMap`, and is obtained by
+/// [`path_key()`].
+pub struct PathKey<S : PathSyntax> {
+    key :    String,
+    syntax : PhantomData<fn() -> S>,
+}
+
+// API functions
+impl<S : PathSyntax> PathKey<S> {
+    /// Creates an instance from `path`.
+    ///
+    /// # Parameters:
+    /// - `path` - the path;
+    /// - `comparison_flags` - flags that moderate the comparison, from
+    ///   [`comparison_flags`];
+    pub fn new(
+        path : &str,
+        comparison_flags : i32,
+    ) -> Self {
+        Self {
+            key :    make_key_::<S>(path, comparison_flags),
+            syntax : PhantomData,
+        }
+    }
+}
+
+// Non-mutating methods
+impl<S : PathSyntax> PathKey<S> {
+    /// The key, as a string, in which all separators are
+    /// [`S::PREFERRED_SEPARATOR`](PathSyntax::PREFERRED_SEPARATOR), runs
+    /// of separators outside of the root are collapsed into one, and all
+    /// characters are [case-folded](PathSyntax::char_fold_case).
+    pub fn as_str(&self) -> &str {
+        &self.key
+    }
+}
+
+// Trait implementations
+
+impl<S : PathSyntax> Clone for PathKey<S> {
+    fn clone(&self) -> Self {
+        Self {
+            key :    self.key.clone(),
+            syntax : PhantomData,
+        }
+    }
+}
+
+impl<S : PathSyntax> fmt::Debug for PathKey<S> {
+    fn fmt(
+        &self,
+        f : &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_tuple("PathKey").field(&self.key).finish()
+    }
+}
+
+impl<S : PathSyntax> PartialEq for PathKey<S> {
+    fn eq(
+        &self,
+        other : &Self,
+    ) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<S : PathSyntax> Eq for PathKey<S> {
+}
+
+impl<S : PathSyntax> PartialOrd for PathKey<S> {
+    fn partial_cmp(
+        &self,
+        other : &Self,
+    ) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S : PathSyntax> Ord for PathKey<S> {
+    /// Compares the keys character-wise, except that the separator orders
+    /// before all other characters, so that (as with `std::path::Path`)
+    /// `"a/b"` orders before `"a.b"` and `"a-b"`.
+    fn cmp(
+        &self,
+        other : &Self,
+    ) -> Ordering {
+        let ordinal = |c : char| {
+            if S::PREFERRED_SEPARATOR == c {
+                0
+            } else {
+                u32::from(c) + 1
+            }
+        };
+
+        self.key.chars().map(ordinal).cmp(other.key.chars().map(ordinal))
+    }
+}
+
+impl<S : PathSyntax> Hash for PathKey<S> {
+    fn hash<H : Hasher>(
+        &self,
+        state : &mut H,
+    ) {
+        self.key.hash(state);
+    }
+}
+
+
+/// Obtains the [`PathKey`] of `path`, according to the syntax `S`.
+///
+/// # Parameters:
+/// - `path` - the path;
+/// - `comparison_flags` - flags that moderate the comparison, from
+///   [`comparison_flags`];
+pub fn path_key<S : PathSyntax>(
+    path : &str,
+    comparison_flags : i32,
+) -> PathKey<S> {
+    PathKey::new(path, comparison_flags)
+}
+
+/// Evaluates whether two paths are equal according to the rules of the
+/// syntax `S`.
+///
+/// All separators of the syntax are equivalent, as is a run of separators
+/// to a single one - so that `"a//b"` and `"a/b"` are equal - other than
+/// within the root, such as the leading `\\` of a UNC path. Names are
+/// compared case-insensitively if the syntax is not
+/// [case-sensitive](PathSyntax::IS_CASE_SENSITIVE).
+///
+/// # Parameters:
+/// - `lhs` - the left-hand path;
+/// - `rhs` - the right-hand path;
+/// - `comparison_flags` - flags that moderate the comparison, from
+///   [`comparison_flags`];
+///
+/// # Note:
+/// The comparison is lexical, and so does not take account of symbolic
+/// links, nor of the current directory.
+pub fn paths_equal<S : PathSyntax>(
+    lhs : &str,
+    rhs : &str,
+    comparison_flags : i32,
+) -> bool {
+    make_key_::<S>(lhs, comparison_flags) == make_key_::<S>(rhs, comparison_flags)
+}
+
+/// Compares two paths according to the rules of the syntax `S`.
+///
+/// The equivalence of paths is as for [`paths_equal()`], and paths that
+/// are not equal are ordered character-wise, except that separators order
+/// before all other characters.
+///
+/// # Parameters:
+/// - `lhs` - the left-hand path;
+/// - `rhs` - the right-hand path;
+/// - `comparison_flags` - flags that moderate the comparison, from
+///   [`comparison_flags`];
+pub fn path_cmp<S : PathSyntax>(
+    lhs : &str,
+    rhs : &str,
+    comparison_flags : i32,
+) -> Ordering {
+    PathKey::<S>::new(lhs, comparison_flags).cmp(&PathKey::new(rhs, comparison_flags))
+}
+
+
+fn make_key_<S : PathSyntax>(
+    path : &str,
+    flags : i32,
+) -> String {
+    let normalised;
+
+    let path = if 0 != (comparison_flags::NORMALISE & flags) {
+        normalised = normalise::<S>(path);
+
+        normalised.as_str()
+    } else {
+        path
+    };
+
+    #[cfg(feature = "unicode")]
+    let unicode_normalised;
+
+    #[cfg(feature = "unicode")]
+    let path = if 0 != (comparison_flags::UNICODE_NORMALISE & flags) {
+        unicode_normalised = super::normalise_unicode::<S>(path, super::NormalForm::Nfc);
+
+        unicode_normalised.as_str()
+    } else {
+        path
+    };
+
+    let map = |c : char| {
+        if S::char_is_path_name_separator(c) {
+            S::PREFERRED_SEPARATOR
+        } else {
+            S::char_fold_case(c)
+        }
+    };
+
+    // the root is kept as it is, since its separators are significant,
+    // e.g. in "\\server\share"
+
+    let (_, cr) = path_classify::<S>(path, super::classification_flags::IGNORE_INVALID_CHARS);
+
+    let mut key : String = cr.Root.substring_of(path).chars().map(map).collect();
+
+    let min_len = key.len() + S::PREFERRED_SEPARATOR.len_utf8();
+
+    let mut previous_is_separator = false;
+
+    for c in path[cr.Root.len()..].chars().map(map) {
+        let is_separator = S::PREFERRED_SEPARATOR == c;
+
+        if !(is_separator && previous_is_separator) {
+            key.push(c);
+        }
+
+        previous_is_separator = is_separator;
+    }
+
+    // a separator that immediately follows the root is not trailing, e.g.
+    // in "/" or "C:\"
+
+    if 0 != (comparison_flags::IGNORE_TRAILING_SEPARATOR & flags)
+        && key.len() > min_len
+        && key.ends_with(S::PREFERRED_SEPARATOR)
+    {
+        key.pop();
+    }
+
+    key
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate::libpath::util::{
+        unix::UnixSyntax,
+        windows::WindowsSyntax,
+    };
+
+    use alloc::collections::BTreeMap;
+
+
+    #[test]
+    fn TEST_paths_equal_UNIX() {
+        assert!(paths_equal::<UnixSyntax>("a/b", "a/b", 0));
+        assert!(!paths_equal::<UnixSyntax>("a/b", "A/b", 0));
+        assert!(!paths_equal::<UnixSyntax>(r"a\b", "a/b", 0));
+        assert!(!paths_equal::<UnixSyntax>("a/b/", "a/b", 0));
+        assert!(paths_equal::<UnixSyntax>("a//b", "a/b", 0));
+        assert!(paths_equal::<UnixSyntax>("/a///b//", "//a/b/", 0));
+        assert!(!paths_equal::<UnixSyntax>("a//b//", "a/b", 0));
+
+        assert!(paths_equal::<UnixSyntax>(
+            "a/b/",
+            "a/b",
+            comparison_flags::IGNORE_TRAILING_SEPARATOR
+        ));
+        assert!(paths_equal::<UnixSyntax>(
+            "a/b//",
+            "a/b",
+            comparison_flags::IGNORE_TRAILING_SEPARATOR
+        ));
+        assert!(!paths_equal::<UnixSyntax>(
+            "/",
+            "",
+            comparison_flags::IGNORE_TRAILING_SEPARATOR
+        ));
+
+        assert!(paths_equal::<UnixSyntax>(
+            "a//./b/../c",
+            "a/c",
+            comparison_flags::NORMALISE
+        ));
+        assert!(paths_equal::<UnixSyntax>("a/c/", "a/c", comparison_flags::NORMALISE));
+    }
+
+    #[test]
+    fn TEST_paths_equal_WINDOWS() {
+        assert!(paths_equal::<WindowsSyntax>(r"C:\Dir\File.txt", "c:/dir/FILE.TXT", 0));
+        assert!(!paths_equal::<WindowsSyntax>(r"C:\dir", r"D:\dir", 0));
+        assert!(!paths_equal::<WindowsSyntax>(r"C:\dir\", r"C:\dir", 0));
+        assert!(paths_equal::<WindowsSyntax>(r"C:\\dir/\sub", r"C:\dir\sub", 0));
+        assert!(paths_equal::<WindowsSyntax>(
+            r"\\server\share\\dir",
+            r"//server/share/dir",
+            0
+        ));
+        assert!(!paths_equal::<WindowsSyntax>(r"\\server\share", r"\server\share", 0));
+        assert!(!paths_equal::<WindowsSyntax>(r"\\server\share", r"\\\server\share", 0));
+
+        assert!(paths_equal::<WindowsSyntax>(
+            r"C:\dir\",
+            r"c:/DIR",
+            comparison_flags::IGNORE_TRAILING_SEPARATOR
+        ));
+        assert!(!paths_equal::<WindowsSyntax>(
+            r"C:\",
+            "C:",
+            comparison_flags::IGNORE_TRAILING_SEPARATOR
+        ));
+        assert!(paths_equal::<WindowsSyntax>(
+            r"\\server\share\",
+            r"\\SERVER\share\",
+            comparison_flags::IGNORE_TRAILING_SEPARATOR
+        ));
+
+        assert!(paths_equal::<WindowsSyntax>(
+            r"C:\a\..\B",
+            "c:/b",
+            comparison_flags::NORMALISE
+        ));
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn TEST_paths_equal_WITH_UNICODE_NORMALISE() {
+        let nfc = "dir/caf\u{e9}.txt";
+        let nfd = "dir/cafe\u{301}.txt";
+
+        assert!(!paths_equal::<UnixSyntax>(nfc, nfd, 0));
+        assert!(paths_equal::<UnixSyntax>(nfc, nfd, comparison_flags::UNICODE_NORMALISE));
+        assert!(paths_equal::<UnixSyntax>(
+            "dir/./caf\u{e9}.txt/",
+            nfd,
+            comparison_flags::UNICODE_NORMALISE | comparison_flags::NORMALISE
+        ));
+    }
+
+    #[test]
+    fn TEST_path_cmp() {
+        assert_eq!(Ordering::Equal, path_cmp::<WindowsSyntax>(r"a\B", "A/b", 0));
+        assert_eq!(Ordering::Less, path_cmp::<WindowsSyntax>("a/b", "a.b", 0));
+        assert_eq!(Ordering::Less, path_cmp::<WindowsSyntax>("a/z", "a-b", 0));
+        assert_eq!(Ordering::Less, path_cmp::<WindowsSyntax>("a", "B", 0));
+        assert_eq!(Ordering::Greater, path_cmp::<UnixSyntax>("a", "B", 0));
+        assert_eq!(Ordering::Less, path_cmp::<UnixSyntax>("a", "a/b", 0));
+    }
+
+    #[test]
+    fn TEST_PathKey_IN_MAPS() {
+        let mut map = BTreeMap::new();
+
+        map.insert(
+            path_key::<WindowsSyntax>(r"C:\Dir\", comparison_flags::IGNORE_TRAILING_SEPARATOR),
+            1,
+        );
+        map.insert(
+            path_key::<WindowsSyntax>("c:/dir", comparison_flags::IGNORE_TRAILING_SEPARATOR),
+            2,
+        );
+        map.insert(
+            path_key::<WindowsSyntax>("c:/dir/sub", comparison_flags::IGNORE_TRAILING_SEPARATOR),
+            3,
+        );
+
+        assert_eq!(2, map.len());
+        assert_eq!(
+            Some(&2),
+            map.get(&path_key(r"C:\DIR", comparison_flags::IGNORE_TRAILING_SEPARATOR))
+        );
+        assert_eq!(r"C:\DIR", map.keys().next().unwrap().as_str());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn TEST_PathKey_IN_HashMap() {
+        use std::collections::HashMap;
+
+        let mut map = HashMap::new();
+
+        map.insert(path_key::<UnixSyntax>("a/b", 0), 1);
+        map.insert(path_key::<UnixSyntax>("a/./b", comparison_flags::NORMALISE), 2);
+        map.insert(path_key::<UnixSyntax>("A/b", 0), 3);
+        map.insert(path_key::<UnixSyntax>("a//b", 0), 4);
+
+        assert_eq!(2, map.len());
+        assert_eq!(Some(&4), map.get(&path_key("a/b", 0)));
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
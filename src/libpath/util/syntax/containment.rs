@@ -0,0 +1,273 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/syntax/containment.rs
+ *
+ * Purpose: Definition of is_contained_within(), which checks that a path does
+ *          not escape a base directory.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+use super::{
+    normalise,
+    path_classify,
+    PathSyntax,
+};
+use crate::libpath::util::common::{
+    Component,
+    Components,
+};
+
+use alloc::{
+    string::String,
+    vec::Vec,
+};
+use core::fmt;
+
+
+/// The reason that a candidate path is not contained within a base
+/// directory.
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub enum ContainmentError {
+    /// The candidate is not relative, e.g. it is slash-rooted, home-rooted,
+    /// drive-rooted or drive-relative, UNC, or a `\\?\` or device path.
+    NotRelative,
+    /// The candidate ascends, by `".."`, above the base directory.
+    EscapesBase,
+    /// The candidate contains characters that are invalid in the syntax.
+    InvalidChars,
+    /// The candidate contains a name that cannot be used as-is, such as a
+    /// Windows device name - see
+    /// [`PathSyntax::name_is_reserved()`](super::PathSyntax::name_is_reserved).
+    ReservedName,
+}
+
+impl fmt::Display for ContainmentError {
+    fn fmt(
+        &self,
+        f : &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::NotRelative => f.write_str("path is not relative"),
+            Self::EscapesBase => f.write_str("path escapes the base directory"),
+            Self::InvalidChars => f.write_str("path contains invalid characters"),
+            Self::ReservedName => f.write_str("path contains a reserved name"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ContainmentError {
+}
+
+
+/// Determines, lexically, whether `candidate` - a path that is to be
+/// interpreted relative to the directory `base` - is contained within
+/// `base`, according to the syntax `S`.
+///
+/// # Parameters:
+/// - `base` - the base directory;
+/// - `candidate` - the path to be checked, such as the name of an archive
+///   entry or of an uploaded file;
+///
+/// # Returns:
+/// The [`normalise`]d join of `base` and `candidate`, which is safe to use
+/// as a path within `base`.
+///
+/// # Errors:
+/// A [`ContainmentError`] that describes why `candidate` is rejected.
+///
+/// # Note:
+/// Because the check is lexical, it does not detect escapes by way of
+/// symbolic links within `base`.
+pub fn is_contained_within<S : PathSyntax>(
+    base : &str,
+    candidate : &str,
+) -> Result<String, ContainmentError> {
+    let (cl, cr) = path_classify::<S>(candidate, 0);
+
+    if S::INVALID_CHARS == cl {
+        return Err(ContainmentError::InvalidChars);
+    }
+
+    if S::RELATIVE != cl && S::EMPTY != cl {
+        return Err(ContainmentError::NotRelative);
+    }
+
+    let mut parts : Vec<&str> = Vec::new();
+
+    for component in Components::new(candidate, &cr, false, S::char_is_path_name_separator) {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                if parts.pop().is_none() {
+                    return Err(ContainmentError::EscapesBase);
+                }
+            },
+            Component::Normal(name) => {
+                if S::name_is_reserved(name) {
+                    return Err(ContainmentError::ReservedName);
+                }
+
+                parts.push(name);
+            },
+            Component::Prefix(_) | Component::Home(_) | Component::RootDir => {
+                return Err(ContainmentError::NotRelative);
+            },
+        }
+    }
+
+    let mut joined = normalise::<S>(base);
+
+    for part in parts {
+        if !joined.ends_with(S::char_is_path_name_separator) {
+            joined.push(S::PREFERRED_SEPARATOR);
+        }
+
+        joined.push_str(part);
+    }
+
+    Ok(joined)
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate::libpath::util::{
+        unix::UnixSyntax,
+        windows::WindowsSyntax,
+    };
+
+
+    #[test]
+    fn TEST_is_contained_within_WITH_UnixSyntax() {
+        let cases = [
+            ("/srv/uploads", "file.ext", "/srv/uploads/file.ext"),
+            ("/srv/uploads/", "dir/file.ext", "/srv/uploads/dir/file.ext"),
+            ("/srv/uploads", "dir/../file.ext", "/srv/uploads/file.ext"),
+            ("/srv/uploads", "./dir//./file.ext", "/srv/uploads/dir/file.ext"),
+            ("/srv/./uploads/", "", "/srv/uploads"),
+            ("/", "dir", "/dir"),
+            ("uploads", "dir\\..\\..\\file", "uploads/dir\\..\\..\\file"),
+        ];
+
+        for (base, candidate, expected) in cases {
+            assert_eq!(
+                Ok(String::from(expected)),
+                is_contained_within::<UnixSyntax>(base, candidate),
+                "candidate='{candidate}'"
+            );
+        }
+
+        let cases = [
+            ("..", ContainmentError::EscapesBase),
+            ("../file.ext", ContainmentError::EscapesBase),
+            ("dir/../../file.ext", ContainmentError::EscapesBase),
+            ("dir/../dir/../..", ContainmentError::EscapesBase),
+            ("/etc/passwd", ContainmentError::NotRelative),
+            ("//etc/passwd", ContainmentError::NotRelative),
+            ("~/file.ext", ContainmentError::NotRelative),
+            ("dir/fi\0le", ContainmentError::InvalidChars),
+        ];
+
+        for (candidate, expected) in cases {
+            assert_eq!(
+                Err(expected),
+                is_contained_within::<UnixSyntax>("/srv/uploads", candidate),
+                "candidate='{candidate}'"
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_is_contained_within_WITH_WindowsSyntax() {
+        let cases = [
+            (r"C:\uploads", r"file.ext", r"C:\uploads\file.ext"),
+            (r"C:\uploads\", r"dir/file.ext", r"C:\uploads\dir\file.ext"),
+            (r"C:\uploads", r"dir\..\file.ext", r"C:\uploads\file.ext"),
+            (
+                r"\\server\share\uploads",
+                r"dir\file.ext",
+                r"\\server\share\uploads\dir\file.ext",
+            ),
+            (r"C:\", r"dir", r"C:\dir"),
+        ];
+
+        for (base, candidate, expected) in cases {
+            assert_eq!(
+                Ok(String::from(expected)),
+                is_contained_within::<WindowsSyntax>(base, candidate),
+                "candidate='{candidate}'"
+            );
+        }
+
+        let cases = [
+            (r"..\file.ext", ContainmentError::EscapesBase),
+            (r"dir/../../file.ext", ContainmentError::EscapesBase),
+            (r"C:\Windows\win.ini", ContainmentError::NotRelative),
+            (r"C:..\file.ext", ContainmentError::NotRelative),
+            (r"C:file.ext", ContainmentError::NotRelative),
+            (r"\Windows\win.ini", ContainmentError::NotRelative),
+            (r"/Windows/win.ini", ContainmentError::NotRelative),
+            (r"\\server\share\file.ext", ContainmentError::NotRelative),
+            (r"\\?\C:\Windows\win.ini", ContainmentError::NotRelative),
+            (r"\\.\COM1", ContainmentError::NotRelative),
+            (r"~\file.ext", ContainmentError::NotRelative),
+            (r"file.ext:stream", ContainmentError::InvalidChars),
+            (r"dir\what?", ContainmentError::InvalidChars),
+            (r"dir\CON", ContainmentError::ReservedName),
+            (r"nul.txt", ContainmentError::ReservedName),
+            (r"dir\com1\file.ext", ContainmentError::ReservedName),
+            (r"dir\..  \file.ext", ContainmentError::ReservedName),
+            (r"dir\file.ext.", ContainmentError::ReservedName),
+        ];
+
+        for (candidate, expected) in cases {
+            assert_eq!(
+                Err(expected),
+                is_contained_within::<WindowsSyntax>(r"C:\uploads", candidate),
+                "candidate='{candidate}'"
+            );
+        }
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -0,0 +1,377 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/syntax/join.rs
+ *
+ * Purpose: Lexical joining of paths, and obtaining of one path relative to
+ *          another, for any syntax.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+use super::{
+    classification_flags::IGNORE_INVALID_CHARS,
+    normalise,
+    path_classify,
+    PathSyntax,
+};
+use crate::libpath::util::common::{
+    Component,
+    Components,
+};
+
+use alloc::{
+    string::String,
+    vec::Vec,
+};
+use core::fmt;
+
+
+/// The reason that a path cannot be expressed relative to a base
+/// directory.
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub enum RelativePathError {
+    /// The path and the base directory have different roots, e.g. `"/a"`
+    /// and `"a"`, or `"C:\a"` and `"D:\a"`.
+    DifferentRoots,
+    /// The base directory ascends, by `".."`, above the point at which it
+    /// diverges from the path, and so the names required to descend back
+    /// again are unknown.
+    BaseAscends,
+}
+
+impl fmt::Display for RelativePathError {
+    fn fmt(
+        &self,
+        f : &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::DifferentRoots => f.write_str("path and base directory have different roots"),
+            Self::BaseAscends => f.write_str("base directory ascends above the common directory"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RelativePathError {
+}
+
+
+/// Joins `path` to `base`, lexically, according to the syntax `S`.
+///
+/// # Parameters:
+/// - `base` - the base directory;
+/// - `path` - the path to be joined to `base`;
+///
+/// # Returns:
+/// `base` if `path` is empty; `path` if it is not relative; otherwise
+/// `base` and `path` separated, if `base` does not already end with one,
+/// by [`S::PREFERRED_SEPARATOR`](PathSyntax::PREFERRED_SEPARATOR). No
+/// separator is added after a `base` that is solely a drive, such as
+/// `"C:"`, so that the result remains relative to that drive's current
+/// directory.
+///
+/// # Note:
+/// A `path` that is not relative replaces `base` entirely, even when it
+/// is a Windows path such as `"C:file"` or `"\dir"` that Windows would
+/// resolve against the drive of `base`. The result is not normalised -
+/// see [`normalise()`](super::normalise()).
+pub fn join<S : PathSyntax>(
+    base : &str,
+    path : &str,
+) -> String {
+    let (cl, _) = path_classify::<S>(path, IGNORE_INVALID_CHARS);
+
+    if S::EMPTY == cl {
+        return base.into();
+    }
+
+    if S::RELATIVE != cl {
+        return path.into();
+    }
+
+    let mut joined = String::from(base);
+
+    if !joined.is_empty() && !joined.ends_with(S::char_is_path_name_separator) && !is_drive_root_::<S>(base) {
+        joined.push(S::PREFERRED_SEPARATOR);
+    }
+
+    joined.push_str(path);
+
+    joined
+}
+
+/// Obtains, lexically, the path that identifies `path` when it is joined
+/// to the directory `base`, according to the syntax `S`.
+///
+/// # Parameters:
+/// - `base` - the base directory;
+/// - `path` - the path to be expressed relative to `base`;
+///
+/// # Returns:
+/// The relative path, which is `"."` if `path` and `base` are the same
+/// directory, and whose separators are all
+/// [`S::PREFERRED_SEPARATOR`](PathSyntax::PREFERRED_SEPARATOR).
+///
+/// # Errors:
+/// A [`RelativePathError`] that describes why `path` cannot be expressed
+/// relative to `base`.
+///
+/// # Note:
+/// Both paths are [`normalise()`](super::normalise())d before they are
+/// compared, and names are compared according to
+/// [`S::char_fold_case()`](PathSyntax::char_fold_case), so that, e.g.,
+/// `"C:\Dir\file"` relative to `"c:\dir"` is `"file"`.
+pub fn relative_path<S : PathSyntax>(
+    base : &str,
+    path : &str,
+) -> Result<String, RelativePathError> {
+    let base = normalise::<S>(base);
+    let path = normalise::<S>(path);
+
+    let (base_root, base_parts) = split_::<S>(&base);
+    let (path_root, path_parts) = split_::<S>(&path);
+
+    if !names_equal_::<S>(&base_root, &path_root) {
+        return Err(RelativePathError::DifferentRoots);
+    }
+
+    let num_common = base_parts
+        .iter()
+        .zip(path_parts.iter())
+        .take_while(|(b, p)| names_equal_::<S>(b, p))
+        .count();
+
+    if base_parts[num_common..].contains(&"..") {
+        return Err(RelativePathError::BaseAscends);
+    }
+
+    let mut relative = String::new();
+
+    let ascents = core::iter::repeat_n("..", base_parts.len() - num_common);
+    let descents = path_parts[num_common..].iter().copied();
+
+    for part in ascents.chain(descents) {
+        if !relative.is_empty() {
+            relative.push(S::PREFERRED_SEPARATOR);
+        }
+
+        relative.push_str(part);
+    }
+
+    if relative.is_empty() {
+        relative.push('.');
+    }
+
+    Ok(relative)
+}
+
+
+/// Splits a normalised path into its root - comprising any prefix or home
+/// designator and any root directory - and its names.
+fn split_<S : PathSyntax>(
+    path : &str
+) -> (
+    String,    // root
+    Vec<&str>, // parts
+) {
+    let (cl, cr) = path_classify::<S>(path, IGNORE_INVALID_CHARS);
+
+    let mut root = String::new();
+    let mut parts = Vec::new();
+
+    for component in Components::new(path, &cr, S::is_home_rooted(cl), S::char_is_path_name_separator) {
+        match component {
+            Component::Prefix(prefix) | Component::Home(prefix) => root.push_str(prefix),
+            Component::RootDir => root.push(S::PREFERRED_SEPARATOR),
+            Component::CurDir => (),
+            Component::ParentDir => parts.push(".."),
+            Component::Normal(name) => parts.push(name),
+        }
+    }
+
+    (root, parts)
+}
+
+/// Evaluates whether `path` consists solely of a root that ends with a
+/// drive designator, such as `"C:"`.
+fn is_drive_root_<S : PathSyntax>(path : &str) -> bool {
+    let (cl, cr) = path_classify::<S>(path, IGNORE_INVALID_CHARS);
+
+    !S::is_home_rooted(cl) && path.len() == cr.Root.len() && path.ends_with(':')
+}
+
+fn names_equal_<S : PathSyntax>(
+    lhs : &str,
+    rhs : &str,
+) -> bool {
+    lhs.chars()
+        .map(S::char_fold_case)
+        .eq(rhs.chars().map(S::char_fold_case))
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate::libpath::util::{
+        unix::UnixSyntax,
+        windows::WindowsSyntax,
+    };
+
+
+    #[test]
+    fn TEST_join_WITH_UnixSyntax() {
+        let cases = [
+            ("/usr", "bin", "/usr/bin"),
+            ("/usr/", "bin/ls", "/usr/bin/ls"),
+            ("usr", "../bin", "usr/../bin"),
+            ("", "bin", "bin"),
+            ("/usr", "", "/usr"),
+            ("/usr", "/etc", "/etc"),
+            ("/usr", "~/bin", "~/bin"),
+            ("/usr", "dir\\file", "/usr/dir\\file"),
+        ];
+
+        for (base, path, expected) in cases {
+            assert_eq!(expected, join::<UnixSyntax>(base, path), "base='{base}', path='{path}'");
+        }
+    }
+
+    #[test]
+    fn TEST_join_WITH_WindowsSyntax() {
+        let cases = [
+            (r"C:\dir", r"file.ext", r"C:\dir\file.ext"),
+            (r"C:\dir/", r"sub/file.ext", r"C:\dir/sub/file.ext"),
+            (r"C:", r"file.ext", r"C:file.ext"),
+            (r"\\server\share", r"file.ext", r"\\server\share\file.ext"),
+            (r"C:\dir", r"D:\file.ext", r"D:\file.ext"),
+            (r"C:\dir", r"D:file.ext", r"D:file.ext"),
+            (r"C:\dir", r"\file.ext", r"\file.ext"),
+            (r"C:\dir", r"\\server\share", r"\\server\share"),
+        ];
+
+        for (base, path, expected) in cases {
+            assert_eq!(
+                expected,
+                join::<WindowsSyntax>(base, path),
+                "base='{base}', path='{path}'"
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_relative_path_WITH_UnixSyntax() {
+        let cases = [
+            ("/usr/local", "/usr/local/bin/ls", "bin/ls"),
+            ("/usr/local/", "/usr/local", "."),
+            ("/usr/local", "/usr/bin", "../bin"),
+            ("/usr/local/lib", "/etc", "../../../etc"),
+            ("/", "/etc/passwd", "etc/passwd"),
+            ("a/./b", "a/c/../d", "../d"),
+            ("", "a/b", "a/b"),
+            ("a/b", "", "../.."),
+            ("../a", "../b", "../b"),
+            ("..", "../..", ".."),
+            ("~/src", "~/doc", "../doc"),
+            ("/Usr", "/usr/bin", "../usr/bin"),
+        ];
+
+        for (base, path, expected) in cases {
+            assert_eq!(
+                Ok(String::from(expected)),
+                relative_path::<UnixSyntax>(base, path),
+                "base='{base}', path='{path}'"
+            );
+        }
+
+        let cases = [
+            ("/usr", "usr/bin", RelativePathError::DifferentRoots),
+            ("usr", "/usr/bin", RelativePathError::DifferentRoots),
+            ("~/src", "/src", RelativePathError::DifferentRoots),
+            ("..", "a", RelativePathError::BaseAscends),
+            ("a/../..", "b", RelativePathError::BaseAscends),
+        ];
+
+        for (base, path, expected) in cases {
+            assert_eq!(
+                Err(expected),
+                relative_path::<UnixSyntax>(base, path),
+                "base='{base}', path='{path}'"
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_relative_path_WITH_WindowsSyntax() {
+        let cases = [
+            (r"C:\Program Files", r"C:\Program Files\app\app.exe", r"app\app.exe"),
+            (r"c:\dir", r"C:\Dir\file", r"file"),
+            (r"C:/a/b", r"C:\a\c", r"..\c"),
+            (r"C:a", r"C:b", r"..\b"),
+            (r"\\server\share\a", r"\\SERVER\share\b\c", r"..\b\c"),
+            (r"dir", r"dir/sub/file", r"sub\file"),
+        ];
+
+        for (base, path, expected) in cases {
+            assert_eq!(
+                Ok(String::from(expected)),
+                relative_path::<WindowsSyntax>(base, path),
+                "base='{base}', path='{path}'"
+            );
+        }
+
+        let cases = [
+            (r"C:\dir", r"D:\dir", RelativePathError::DifferentRoots),
+            (r"C:\dir", r"C:dir", RelativePathError::DifferentRoots),
+            (r"C:\dir", r"\dir", RelativePathError::DifferentRoots),
+            (r"\\server\share", r"\\server\other", RelativePathError::DifferentRoots),
+        ];
+
+        for (base, path, expected) in cases {
+            assert_eq!(
+                Err(expected),
+                relative_path::<WindowsSyntax>(base, path),
+                "base='{base}', path='{path}'"
+            );
+        }
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -0,0 +1,199 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/syntax/normalise.rs
+ *
+ * Purpose: Lexical normalisation of paths.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+use super::{
+    path_classify,
+    PathSyntax,
+};
+use crate::libpath::util::common::{
+    Component,
+    Components,
+};
+
+use alloc::{
+    string::String,
+    vec::Vec,
+};
+
+
+/// Normalises `path` lexically - i.e. without reference to the
+/// file-system - according to the syntax `S`.
+///
+/// Runs of separators are collapsed, `"."` parts are removed, `".."` parts
+/// are resolved against the preceding name (or discarded, if they would
+/// ascend above a root), all separators are made
+/// [`S::PREFERRED_SEPARATOR`](PathSyntax::PREFERRED_SEPARATOR), and any
+/// trailing separator is removed.
+///
+/// # Returns:
+/// The normalised path, which is `"."` if `path` is relative and
+/// normalises to nothing.
+///
+/// # Note:
+/// Because it is lexical, the normalisation does not take account of
+/// symbolic links, and so `"a/link/.."` becomes `"a"` even when `link`
+/// refers to a directory elsewhere.
+pub fn normalise<S : PathSyntax>(path : &str) -> String {
+    let (cl, cr) = path_classify::<S>(path, super::classification_flags::IGNORE_INVALID_CHARS);
+
+    let mut root = String::new();
+    let mut is_home = false;
+    let mut is_rooted = false;
+    let mut parts : Vec<&str> = Vec::new();
+
+    for component in Components::new(path, &cr, S::is_home_rooted(cl), S::char_is_path_name_separator) {
+        match component {
+            Component::Prefix(prefix) => {
+                root.extend(prefix.chars().map(|c| {
+                    if S::char_is_path_name_separator(c) {
+                        S::PREFERRED_SEPARATOR
+                    } else {
+                        c
+                    }
+                }));
+            },
+            Component::Home(home) => {
+                root.push_str(home);
+
+                is_home = true;
+            },
+            Component::RootDir => is_rooted = true,
+            Component::CurDir => (),
+            Component::ParentDir => {
+                match parts.last() {
+                    Some(&"..") | None => {
+                        if !is_rooted {
+                            parts.push("..");
+                        }
+                    },
+                    Some(_) => {
+                        parts.pop();
+                    },
+                }
+            },
+            Component::Normal(name) => parts.push(name),
+        }
+    }
+
+    let mut normalised = root;
+
+    if is_rooted {
+        normalised.push(S::PREFERRED_SEPARATOR);
+    }
+
+    for (index, part) in parts.iter().enumerate() {
+        if 0 != index || is_home {
+            normalised.push(S::PREFERRED_SEPARATOR);
+        }
+
+        normalised.push_str(part);
+    }
+
+    if normalised.is_empty() {
+        normalised.push('.');
+    }
+
+    normalised
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate::libpath::util::{
+        unix::UnixSyntax,
+        windows::WindowsSyntax,
+    };
+
+
+    #[test]
+    fn TEST_normalise_WITH_UnixSyntax() {
+        let cases = [
+            ("", "."),
+            (".", "."),
+            ("./", "."),
+            ("a/b/../c", "a/c"),
+            ("a//b/./c/", "a/b/c"),
+            ("../a/../..", "../.."),
+            ("a/..", "."),
+            ("/", "/"),
+            ("/..", "/"),
+            ("/a/../../b", "/b"),
+            ("//a///b", "/a/b"),
+            ("~", "~"),
+            ("~/a/./b/..", "~/a"),
+            ("~/..", "~/.."),
+            ("a\\b/../c", "c"),
+        ];
+
+        for (path, expected) in cases {
+            assert_eq!(expected, normalise::<UnixSyntax>(path), "path='{path}'");
+        }
+    }
+
+    #[test]
+    fn TEST_normalise_WITH_WindowsSyntax() {
+        let cases = [
+            ("", "."),
+            (r"a\b\..\c", r"a\c"),
+            (r"a/b\./c\", r"a\b\c"),
+            (r"..\a\..\..", r"..\.."),
+            (r"C:\", r"C:\"),
+            (r"C:\..\a", r"C:\a"),
+            (r"C:/a/./b", r"C:\a\b"),
+            (r"C:", r"C:"),
+            (r"C:a\..\..\b", r"C:..\b"),
+            (r"\a\..\b", r"\b"),
+            (r"\\server\share\a\..\b", r"\\server\share\b"),
+            (r"//server/share/a/../..", r"\\server\share\"),
+            (r"\\?\C:\a\..\b", r"\\?\C:\b"),
+        ];
+
+        for (path, expected) in cases {
+            assert_eq!(expected, normalise::<WindowsSyntax>(path), "path='{path}'");
+        }
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -0,0 +1,518 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/syntax/path_arena.rs
+ *
+ * Purpose: Definition of PathArena, compact storage for large numbers of paths.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+use super::{
+    classification_flags,
+    path_classify,
+    ClassifiedPath,
+    PathSyntax,
+};
+
+use alloc::{
+    collections::BTreeMap,
+    string::String,
+    vec::Vec,
+};
+use core::{
+    fmt,
+    marker::PhantomData,
+};
+
+
+/// A lightweight handle to a path held in a [`PathArena`].
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Hash)]
+pub struct PathHandle(u32);
+
+// Non-mutating methods
+impl PathHandle {
+    /// The index of the path within its arena, in order of insertion.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+
+/// Compact storage for large numbers of paths, which are classified
+/// according to the syntax `S`.
+///
+/// Each path is split into its location (its root and directory) and its
+/// entry name. The location is interned one directory at a time, so that
+/// a directory that is shared by many paths is held only once, and each
+/// path is then held as a reference to its (interned) directory and the
+/// text of its entry name, using `u32` offsets. Paths are rebuilt - as
+/// `String`s or as [`ClassifiedPath`]s - on demand, and are identical to
+/// those inserted.
+///
+/// # Note:
+/// [`unix::PathArena`](crate::libpath::util::unix::PathArena) and
+/// [`windows::PathArena`](crate::libpath::util::windows::PathArena) are
+/// aliases of this for their respective syntaxes.
+pub struct PathArena<S : PathSyntax> {
+    /// The text of all the entry names and directory segments.
+    text :            String,
+    directories :     Vec<Record_>,
+    /// (parent, hash of segment) => directory; the segments themselves are
+    /// held only in `text`.
+    directory_index : BTreeMap<(u32, u64), u32>,
+    /// directory => the directory interned before it with the same parent
+    /// and hash of segment, in the (rare) event of a hash collision.
+    collisions :      BTreeMap<u32, u32>,
+    entries :         Vec<Record_>,
+    syntax :          PhantomData<fn() -> S>,
+}
+
+/// A directory segment or entry name, held in the arena's text, and the
+/// directory that precedes it.
+#[derive(Clone, Copy)]
+struct Record_ {
+    parent : u32,
+    offset : u32,
+    length : u32,
+}
+
+const NO_DIRECTORY_ : u32 = u32::MAX;
+
+// API functions
+impl<S : PathSyntax> PathArena<S> {
+    /// Creates an empty instance.
+    pub fn new() -> Self {
+        Self {
+            text :            String::new(),
+            directories :     Vec::new(),
+            directory_index : BTreeMap::new(),
+            collisions :      BTreeMap::new(),
+            entries :         Vec::new(),
+            syntax :          PhantomData,
+        }
+    }
+}
+
+// Mutating methods
+impl<S : PathSyntax> PathArena<S> {
+    /// Inserts `path`, obtaining a handle to it.
+    ///
+    /// Each insertion obtains a new handle, even if an identical path has
+    /// already been inserted.
+    ///
+    /// # Preconditions:
+    /// * the total length of the text held, and the number of paths and of
+    ///   directories held, do not exceed `u32::MAX - 1` - will panic
+    ///   otherwise;
+    pub fn insert(
+        &mut self,
+        path : &str,
+    ) -> PathHandle {
+        let (_, cr) = path_classify::<S>(path, classification_flags::IGNORE_INVALID_CHARS);
+
+        let root_len = cr.Root.len();
+        let location_len = cr.EntryName.offset;
+
+        let mut directory = NO_DIRECTORY_;
+
+        if 0 != root_len {
+            directory = self.intern_directory_(directory, &path[..root_len]);
+        }
+
+        // each directory segment is a name and the separator(s) that
+        // follow it, so that the path is rebuilt exactly
+
+        let mut segment_start = root_len;
+        let mut previous_is_separator = false;
+
+        for (index, c) in path[root_len..location_len]
+            .char_indices()
+            .map(|(i, c)| (root_len + i, c))
+        {
+            let is_separator = S::char_is_path_name_separator(c);
+
+            if previous_is_separator && !is_separator {
+                directory = self.intern_directory_(directory, &path[segment_start..index]);
+
+                segment_start = index;
+            }
+
+            previous_is_separator = is_separator;
+        }
+
+        if segment_start != location_len {
+            directory = self.intern_directory_(directory, &path[segment_start..location_len]);
+        }
+
+        let entry = self.push_record_(directory, &path[location_len..]);
+
+        self.entries.push(entry);
+
+        PathHandle(to_u32_(self.entries.len() - 1))
+    }
+
+    fn intern_directory_(
+        &mut self,
+        parent : u32,
+        segment : &str,
+    ) -> u32 {
+        let key = (parent, hash_segment_(segment));
+
+        let mut candidate = self.directory_index.get(&key).copied();
+
+        while let Some(directory) = candidate {
+            if segment == self.text_of_(&self.directories[directory as usize]) {
+                return directory;
+            }
+
+            candidate = self.collisions.get(&directory).copied();
+        }
+
+        let record = self.push_record_(parent, segment);
+        let directory = to_u32_(self.directories.len());
+
+        self.directories.push(record);
+
+        if let Some(previous) = self.directory_index.insert(key, directory) {
+            self.collisions.insert(directory, previous);
+        }
+
+        directory
+    }
+
+    fn push_record_(
+        &mut self,
+        parent : u32,
+        s : &str,
+    ) -> Record_ {
+        let offset = to_u32_(self.text.len());
+
+        self.text.push_str(s);
+
+        Record_ {
+            parent,
+            offset,
+            length : to_u32_(s.len()),
+        }
+    }
+}
+
+// Non-mutating methods
+impl<S : PathSyntax> PathArena<S> {
+    /// The number of paths held.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Indicates whether no paths are held.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The number of distinct directories held.
+    pub fn num_directories(&self) -> usize {
+        self.directories.len()
+    }
+
+    /// Obtains an iterator over the handles of the paths held, in order
+    /// of insertion.
+    pub fn handles(&self) -> impl Iterator<Item = PathHandle> {
+        (0..to_u32_(self.entries.len())).map(PathHandle)
+    }
+
+    /// Obtains the entry name of a path, without rebuilding it.
+    ///
+    /// # Preconditions:
+    /// * `handle` was obtained from this instance - will panic, or obtain
+    ///   an unrelated name, otherwise;
+    pub fn entry_name(
+        &self,
+        handle : PathHandle,
+    ) -> &str {
+        self.text_of_(&self.entries[handle.index()])
+    }
+
+    /// Rebuilds a path.
+    ///
+    /// # Preconditions:
+    /// * `handle` was obtained from this instance - will panic, or obtain
+    ///   an unrelated path, otherwise;
+    pub fn path(
+        &self,
+        handle : PathHandle,
+    ) -> String {
+        let mut path = String::new();
+
+        self.write_path(handle, &mut path);
+
+        path
+    }
+
+    /// Rebuilds a path, appending it to `buffer`, which allows a buffer to
+    /// be reused when visiting many paths.
+    ///
+    /// # Preconditions:
+    /// * `handle` was obtained from this instance - will panic, or obtain
+    ///   an unrelated path, otherwise;
+    pub fn write_path(
+        &self,
+        handle : PathHandle,
+        buffer : &mut String,
+    ) {
+        let entry = &self.entries[handle.index()];
+
+        let mut segments = Vec::new();
+        let mut directory = entry.parent;
+
+        while NO_DIRECTORY_ != directory {
+            let record = &self.directories[directory as usize];
+
+            segments.push(self.text_of_(record));
+
+            directory = record.parent;
+        }
+
+        buffer.reserve(segments.iter().map(|s| s.len()).sum::<usize>() + entry.length as usize);
+        buffer.extend(segments.into_iter().rev());
+        buffer.push_str(self.text_of_(entry));
+    }
+
+    /// Rebuilds a path, and classifies it.
+    ///
+    /// # Parameters:
+    /// - `handle` - the handle of the path;
+    /// - `parse_flags` - flags that moderate the classification;
+    ///
+    /// # Preconditions:
+    /// * `handle` was obtained from this instance - will panic, or obtain
+    ///   an unrelated path, otherwise;
+    pub fn classified_path(
+        &self,
+        handle : PathHandle,
+        parse_flags : i32,
+    ) -> ClassifiedPath<'static, S> {
+        ClassifiedPath::from_string(self.path(handle), parse_flags)
+    }
+
+    fn text_of_(
+        &self,
+        record : &Record_,
+    ) -> &str {
+        let offset = record.offset as usize;
+
+        &self.text[offset..offset + record.length as usize]
+    }
+}
+
+// Trait implementations
+
+impl<S : PathSyntax> Default for PathArena<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S : PathSyntax> fmt::Debug for PathArena<S> {
+    fn fmt(
+        &self,
+        f : &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_struct("PathArena")
+            .field("len", &self.len())
+            .field("num_directories", &self.num_directories())
+            .field("text_len", &self.text.len())
+            .finish()
+    }
+}
+
+impl<'a, S : PathSyntax> Extend<&'a str> for PathArena<S> {
+    fn extend<I : IntoIterator<Item = &'a str>>(
+        &mut self,
+        iter : I,
+    ) {
+        for path in iter {
+            self.insert(path);
+        }
+    }
+}
+
+
+/// Obtains the 64-bit FNV-1a hash of a directory segment.
+fn hash_segment_(segment : &str) -> u64 {
+    segment.bytes().fold(0xCBF2_9CE4_8422_2325, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01B3)
+    })
+}
+
+fn to_u32_(n : usize) -> u32 {
+    match u32::try_from(n) {
+        Ok(n) if NO_DIRECTORY_ != n => n,
+        _ => panic!("PathArena capacity exceeded"),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate::libpath::util::{
+        unix::UnixSyntax,
+        windows::{
+            self,
+            WindowsSyntax,
+        },
+    };
+
+
+    #[test]
+    fn TEST_PathArena_ROUNDTRIP_UNIX() {
+        let paths = [
+            "",
+            "/",
+            "file.ext",
+            "/usr/local/bin/tool",
+            "/usr/local/bin/other",
+            "/usr//local/bin/tool",
+            "/usr/local/",
+            "~/dir/file",
+            "dir/sub-dir/",
+            "./../x",
+        ];
+
+        let mut arena = PathArena::<UnixSyntax>::new();
+
+        let handles : Vec<_> = paths.iter().map(|path| arena.insert(path)).collect();
+
+        assert_eq!(paths.len(), arena.len());
+
+        for (path, handle) in paths.iter().zip(handles) {
+            assert_eq!(*path, arena.path(handle), "path='{path}'");
+        }
+    }
+
+    #[test]
+    fn TEST_PathArena_ROUNDTRIP_WINDOWS() {
+        let paths = [
+            r"C:\Windows\System32\drivers\etc\hosts",
+            r"C:\Windows\System32\notepad.exe",
+            r"C:/Windows\System32/cmd.exe",
+            r"\\server\share\dir\file.txt",
+            r"\\?\C:\dir\file.txt",
+            r"C:file.txt",
+            r"\\.\COM1",
+        ];
+
+        let mut arena = PathArena::<WindowsSyntax>::new();
+
+        arena.extend(paths);
+
+        for (path, handle) in paths.iter().zip(arena.handles()) {
+            assert_eq!(*path, arena.path(handle), "path='{path}'");
+        }
+    }
+
+    #[test]
+    fn TEST_PathArena_INTERNS_DIRECTORIES() {
+        let mut arena = PathArena::<UnixSyntax>::new();
+
+        for i in 0..100 {
+            arena.insert(&format!("/usr/local/share/doc/file-{i}.txt"));
+        }
+
+        // "/", "usr/", "local/", "share/", "doc/"
+
+        assert_eq!(5, arena.num_directories());
+        assert_eq!(100, arena.len());
+        assert!(arena.text.len() < 100 * "/usr/local/share/doc/file-99.txt".len() / 2);
+
+        let handle = arena.handles().nth(42).unwrap();
+
+        assert_eq!(42, handle.index());
+        assert_eq!("file-42.txt", arena.entry_name(handle));
+
+        let mut buffer = String::from("> ");
+
+        arena.write_path(handle, &mut buffer);
+
+        assert_eq!("> /usr/local/share/doc/file-42.txt", buffer);
+    }
+
+    #[test]
+    fn TEST_PathArena_WITH_HASH_COLLISIONS() {
+        let mut arena = PathArena::<UnixSyntax>::new();
+
+        let a = arena.intern_directory_(NO_DIRECTORY_, "a/");
+        let b = arena.intern_directory_(NO_DIRECTORY_, "b/");
+
+        // simulate a collision, by filing "b/" under the hash of "a/"
+
+        arena.directory_index.remove(&(NO_DIRECTORY_, hash_segment_("b/")));
+        arena.directory_index.insert((NO_DIRECTORY_, hash_segment_("a/")), b);
+        arena.collisions.insert(b, a);
+
+        assert_eq!(a, arena.intern_directory_(NO_DIRECTORY_, "a/"));
+        assert_eq!(2, arena.num_directories());
+
+        let c = arena.intern_directory_(NO_DIRECTORY_, "b/");
+
+        assert_ne!(b, c);
+        assert_eq!(3, arena.num_directories());
+        assert_eq!(c, arena.intern_directory_(NO_DIRECTORY_, "b/"));
+        assert_eq!(a, arena.intern_directory_(NO_DIRECTORY_, "a/"));
+    }
+
+    #[test]
+    fn TEST_PathArena_classified_path() {
+        let mut arena = PathArena::<WindowsSyntax>::new();
+
+        let path = r"C:\dir\sub-dir\file.ext";
+        let handle = arena.insert(path);
+
+        let cp = arena.classified_path(handle, 0);
+        let (cl, cr) = windows::path_classify(path, 0);
+
+        assert_eq!(path, cp.as_str());
+        assert_eq!(cl, cp.classification());
+        assert_eq!(&cr, cp.result());
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
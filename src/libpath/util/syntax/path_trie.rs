@@ -0,0 +1,631 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/syntax/path_trie.rs
+ *
+ * Purpose: Definition of PathTrie, a map from paths to values that supports
+ *          prefix queries.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+use super::{
+    classification_flags,
+    path_classify,
+    PathSyntax,
+};
+
+use alloc::{
+    collections::BTreeMap,
+    string::String,
+    vec::Vec,
+};
+use core::{
+    fmt,
+    marker::PhantomData,
+};
+
+
+/// A map from paths to values, organised by the components of the paths
+/// according to the syntax `S`, that supports efficient prefix queries.
+///
+/// Each path is classified, and is keyed by its root (including any
+/// following separator) and then by each of its names. Components are
+/// compared according to the case rules of the syntax - so that a
+/// Windows trie is case-insensitive - and all separators are equivalent.
+///
+/// # Note:
+/// [`unix::PathTrie`](crate::libpath::util::unix::PathTrie) and
+/// [`windows::PathTrie`](crate::libpath::util::windows::PathTrie) are
+/// aliases of this for their respective syntaxes.
+///
+/// Paths are keyed lexically, and so `"."` and `".."` parts are treated as
+/// names. Apply [`normalise()`](super::normalise()) first if they may be
+/// present.
+pub struct PathTrie<V, S : PathSyntax> {
+    root :   Node_<V>,
+    len :    usize,
+    syntax : PhantomData<fn() -> S>,
+}
+
+struct Node_<V> {
+    /// The component, as it was first inserted.
+    name :     String,
+    value :    Option<V>,
+    children : BTreeMap<String, Node_<V>>,
+}
+
+impl<V> Node_<V> {
+    fn new(name : &str) -> Self {
+        Self {
+            name :     String::from(name),
+            value :    None,
+            children : BTreeMap::new(),
+        }
+    }
+}
+
+// API functions
+impl<V, S : PathSyntax> PathTrie<V, S> {
+    /// Creates an empty instance.
+    pub fn new() -> Self {
+        Self {
+            root :   Node_::new(""),
+            len :    0,
+            syntax : PhantomData,
+        }
+    }
+}
+
+// Mutating methods
+impl<V, S : PathSyntax> PathTrie<V, S> {
+    /// Inserts `value` for `path`.
+    ///
+    /// # Returns:
+    /// The value previously held for `path`, if any.
+    pub fn insert(
+        &mut self,
+        path : &str,
+        value : V,
+    ) -> Option<V> {
+        let mut node = &mut self.root;
+
+        for component in components_::<S>(path) {
+            node = node
+                .children
+                .entry(key_::<S>(component))
+                .or_insert_with(|| Node_::new(component));
+        }
+
+        let previous = node.value.replace(value);
+
+        if previous.is_none() {
+            self.len += 1;
+        }
+
+        previous
+    }
+
+    /// Obtains a mutable reference to the value held for `path`, if any.
+    pub fn get_mut(
+        &mut self,
+        path : &str,
+    ) -> Option<&mut V> {
+        let mut node = &mut self.root;
+
+        for component in components_::<S>(path) {
+            node = node.children.get_mut(&key_::<S>(component))?;
+        }
+
+        node.value.as_mut()
+    }
+
+    /// Removes the value held for `path`, if any, along with any nodes
+    /// that are thereby left with neither value nor children.
+    ///
+    /// # Returns:
+    /// The value that was held for `path`, if any.
+    pub fn remove(
+        &mut self,
+        path : &str,
+    ) -> Option<V> {
+        let keys : Vec<String> = components_::<S>(path).map(key_::<S>).collect();
+
+        let value = remove_(&mut self.root, &keys);
+
+        if value.is_some() {
+            self.len -= 1;
+        }
+
+        value
+    }
+}
+
+// Non-mutating methods
+impl<V, S : PathSyntax> PathTrie<V, S> {
+    /// The number of paths for which values are held.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Indicates whether no values are held.
+    pub fn is_empty(&self) -> bool {
+        0 == self.len
+    }
+
+    /// Obtains a reference to the value held for `path`, if any.
+    pub fn get(
+        &self,
+        path : &str,
+    ) -> Option<&V> {
+        self.find_(path)?.value.as_ref()
+    }
+
+    /// Indicates whether a value is held for `path`.
+    pub fn contains(
+        &self,
+        path : &str,
+    ) -> bool {
+        self.get(path).is_some()
+    }
+
+    /// Obtains the value held for the longest path that is `path` or one
+    /// of its ancestors, e.g. the value held for `"/usr/local"` when
+    /// `path` is `"/usr/local/bin/tool"`.
+    ///
+    /// # Returns:
+    /// `(prefix : &str, value : &V)`, where `prefix` is the slice of
+    /// `path` that matched, or `None` if no value is held for `path` or any
+    /// of its ancestors.
+    pub fn longest_prefix_match<'p>(
+        &self,
+        path : &'p str,
+    ) -> Option<(
+        &'p str, // prefix
+        &V,      // value
+    )> {
+        let mut node = &self.root;
+        let mut matched = node.value.as_ref().map(|value| (&path[..0], value));
+
+        for component in components_::<S>(path) {
+            match node.children.get(&key_::<S>(component)) {
+                Some(child) => node = child,
+                None => break,
+            }
+
+            if let Some(value) = node.value.as_ref() {
+                let end = component.as_ptr() as usize - path.as_ptr() as usize + component.len();
+
+                matched = Some((&path[..end], value));
+            }
+        }
+
+        matched
+    }
+
+    /// Obtains an iterator over the paths and values held.
+    pub fn iter(&self) -> PathTrieIter<'_, V, S> {
+        PathTrieIter::new(String::new(), 0, Some(&self.root))
+    }
+
+    /// Obtains an iterator over the paths and values held for `path` and
+    /// its descendants.
+    ///
+    /// The paths are formed from the components as they were first
+    /// inserted, separated by
+    /// [`S::PREFERRED_SEPARATOR`](PathSyntax::PREFERRED_SEPARATOR).
+    pub fn subtree(
+        &self,
+        path : &str,
+    ) -> PathTrieIter<'_, V, S> {
+        let mut node = &self.root;
+        let mut prefix = String::new();
+        let mut depth = 0;
+
+        for component in components_::<S>(path) {
+            match node.children.get(&key_::<S>(component)) {
+                Some(child) => {
+                    push_name_::<S>(&mut prefix, depth, &child.name);
+
+                    node = child;
+                    depth += 1;
+                },
+                None => return PathTrieIter::new(String::new(), 0, None),
+            }
+        }
+
+        PathTrieIter::new(prefix, depth, Some(node))
+    }
+
+    fn find_(
+        &self,
+        path : &str,
+    ) -> Option<&Node_<V>> {
+        let mut node = &self.root;
+
+        for component in components_::<S>(path) {
+            node = node.children.get(&key_::<S>(component))?;
+        }
+
+        Some(node)
+    }
+}
+
+// Trait implementations
+
+impl<V, S : PathSyntax> Default for PathTrie<V, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V : fmt::Debug, S : PathSyntax> fmt::Debug for PathTrie<V, S> {
+    fn fmt(
+        &self,
+        f : &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'t, V, S : PathSyntax> IntoIterator for &'t PathTrie<V, S> {
+    type IntoIter = PathTrieIter<'t, V, S>;
+    type Item = (String, &'t V);
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, V, S : PathSyntax> FromIterator<(&'a str, V)> for PathTrie<V, S> {
+    fn from_iter<I : IntoIterator<Item = (&'a str, V)>>(iter : I) -> Self {
+        let mut trie = Self::new();
+
+        for (path, value) in iter {
+            trie.insert(path, value);
+        }
+
+        trie
+    }
+}
+
+
+/// Iterator over the paths and values of a [`PathTrie`], in order of
+/// their keys, obtained by [`PathTrie::iter()`] and
+/// [`PathTrie::subtree()`].
+pub struct PathTrieIter<'t, V, S : PathSyntax> {
+    /// The path, depth, and node of each node yet to be visited.
+    stack :  Vec<(String, usize, &'t Node_<V>)>,
+    syntax : PhantomData<fn() -> S>,
+}
+
+impl<'t, V, S : PathSyntax> PathTrieIter<'t, V, S> {
+    fn new(
+        prefix : String,
+        depth : usize,
+        node : Option<&'t Node_<V>>,
+    ) -> Self {
+        Self {
+            stack :  node.map(|node| (prefix, depth, node)).into_iter().collect(),
+            syntax : PhantomData,
+        }
+    }
+}
+
+impl<'t, V, S : PathSyntax> Iterator for PathTrieIter<'t, V, S> {
+    type Item = (String, &'t V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((path, depth, node)) = self.stack.pop() {
+            for child in node.children.values().rev() {
+                let mut child_path = path.clone();
+
+                push_name_::<S>(&mut child_path, depth, &child.name);
+
+                self.stack.push((child_path, depth + 1, child));
+            }
+
+            if let Some(value) = node.value.as_ref() {
+                return Some((path, value));
+            }
+        }
+
+        None
+    }
+}
+
+
+/// Obtains the components of `path`: its root, including any separator
+/// that follows it (which is empty for a relative path), followed by each
+/// of its names.
+fn components_<S : PathSyntax>(path : &str) -> impl Iterator<Item = &str> {
+    let (_, cr) = path_classify::<S>(path, classification_flags::IGNORE_INVALID_CHARS);
+
+    let mut root_len = cr.Root.len();
+
+    if let Some(c) = path[root_len..].chars().next() {
+        if S::char_is_path_name_separator(c) {
+            root_len += c.len_utf8();
+        }
+    }
+
+    core::iter::once(&path[..root_len]).chain(
+        path[root_len..]
+            .split(S::char_is_path_name_separator)
+            .filter(|name| !name.is_empty()),
+    )
+}
+
+/// Obtains the key of a component, in which all separators are
+/// [`S::PREFERRED_SEPARATOR`](PathSyntax::PREFERRED_SEPARATOR) and all
+/// characters are [case-folded](PathSyntax::char_fold_case).
+fn key_<S : PathSyntax>(component : &str) -> String {
+    component
+        .chars()
+        .map(|c| {
+            if S::char_is_path_name_separator(c) {
+                S::PREFERRED_SEPARATOR
+            } else {
+                S::char_fold_case(c)
+            }
+        })
+        .collect()
+}
+
+/// Appends to `path` - the path of a node at `depth` - the component
+/// `name` of one of its children.
+fn push_name_<S : PathSyntax>(
+    path : &mut String,
+    depth : usize,
+    name : &str,
+) {
+    // A separator is needed only between two names: the root component
+    // either is empty, or ends with a separator, or - as in "C:dir" - is
+    // not followed by one
+
+    if 1 < depth {
+        path.push(S::PREFERRED_SEPARATOR);
+    }
+
+    path.push_str(name);
+}
+
+fn remove_<V>(
+    node : &mut Node_<V>,
+    keys : &[String],
+) -> Option<V> {
+    match keys.split_first() {
+        None => node.value.take(),
+        Some((key, keys)) => {
+            let child = node.children.get_mut(key)?;
+
+            let value = remove_(child, keys);
+
+            if child.value.is_none() && child.children.is_empty() {
+                node.children.remove(key);
+            }
+
+            value
+        },
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate::libpath::util::{
+        unix::UnixSyntax,
+        windows::WindowsSyntax,
+    };
+
+    use alloc::vec;
+
+
+    #[test]
+    fn TEST_PathTrie_insert_AND_get() {
+        let mut trie = PathTrie::<i32, UnixSyntax>::new();
+
+        assert!(trie.is_empty());
+
+        assert_eq!(None, trie.insert("/usr/local", 1));
+        assert_eq!(None, trie.insert("/usr", 2));
+        assert_eq!(None, trie.insert("src/lib.rs", 3));
+        assert_eq!(Some(1), trie.insert("/usr//local/", 4));
+
+        assert_eq!(3, trie.len());
+        assert_eq!(Some(&4), trie.get("/usr/local"));
+        assert_eq!(Some(&2), trie.get("/usr"));
+        assert_eq!(Some(&3), trie.get("src/lib.rs"));
+        assert_eq!(None, trie.get("/"));
+        assert_eq!(None, trie.get("usr"));
+        assert_eq!(None, trie.get("/USR"));
+        assert!(!trie.contains("/usr/local/bin"));
+
+        *trie.get_mut("/usr").unwrap() += 10;
+
+        assert_eq!(Some(&12), trie.get("/usr"));
+    }
+
+    #[test]
+    fn TEST_PathTrie_longest_prefix_match() {
+        let trie : PathTrie<&str, UnixSyntax> = [("/usr", "usr"), ("/usr/local", "local"), ("/", "root")]
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            Some(("/usr/local", &"local")),
+            trie.longest_prefix_match("/usr/local/bin/tool")
+        );
+        assert_eq!(Some(("/usr", &"usr")), trie.longest_prefix_match("/usr/lib"));
+        assert_eq!(Some(("/usr", &"usr")), trie.longest_prefix_match("/usr"));
+        assert_eq!(Some(("/", &"root")), trie.longest_prefix_match("/etc/passwd"));
+        assert_eq!(Some(("/", &"root")), trie.longest_prefix_match("/usrx"));
+        assert_eq!(None, trie.longest_prefix_match("usr/local"));
+    }
+
+    #[test]
+    fn TEST_PathTrie_WINDOWS_IS_CASE_INSENSITIVE() {
+        let mut trie = PathTrie::<i32, WindowsSyntax>::new();
+
+        trie.insert(r"C:\Program Files\App", 1);
+
+        assert_eq!(Some(&1), trie.get("c:/PROGRAM FILES/app"));
+        assert_eq!(
+            Some((r"c:/program files/app", &1)),
+            trie.longest_prefix_match(r"c:/program files/app/bin/app.exe")
+        );
+        assert_eq!(Some(&1), trie.get(r"C:\Program Files\App\"));
+        assert_eq!(None, trie.get(r"C:Program Files\App"));
+        assert_eq!(None, trie.get(r"D:\Program Files\App"));
+    }
+
+    #[test]
+    fn TEST_PathTrie_subtree() {
+        let trie : PathTrie<i32, WindowsSyntax> = [
+            (r"C:\a\b", 1),
+            (r"C:\a\b\c", 2),
+            (r"C:\a\B\d", 3),
+            (r"C:\a\e", 4),
+            (r"C:\x", 5),
+            (r"src\lib.rs", 6),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            vec![
+                (String::from(r"C:\a\b"), &1),
+                (String::from(r"C:\a\b\c"), &2),
+                (String::from(r"C:\a\b\d"), &3)
+            ],
+            trie.subtree("c:/A/B").collect::<Vec<_>>()
+        );
+        assert_eq!(4, trie.subtree(r"C:\a").count());
+        assert_eq!(5, trie.subtree(r"C:\").count());
+        assert_eq!(0, trie.subtree(r"C:\z").count());
+        assert_eq!(6, trie.iter().count());
+        assert_eq!(
+            vec![(String::from(r"src\lib.rs"), &6)],
+            trie.subtree("src").collect::<Vec<_>>()
+        );
+
+        // roots that are not always followed by a separator
+
+        {
+            let trie : PathTrie<i32, WindowsSyntax> = [
+                (r"C:", 1),
+                (r"C:dir", 2),
+                (r"C:dir\a", 3),
+                (r"C:\dir\a", 4),
+                (r"~", 5),
+                (r"~\a", 6),
+            ]
+            .into_iter()
+            .collect();
+
+            assert_eq!(
+                vec![
+                    (String::from(r"C:"), &1),
+                    (String::from(r"C:dir"), &2),
+                    (String::from(r"C:dir\a"), &3)
+                ],
+                trie.subtree("C:").collect::<Vec<_>>()
+            );
+            assert_eq!(
+                vec![(String::from(r"C:dir"), &2), (String::from(r"C:dir\a"), &3)],
+                trie.subtree("c:DIR").collect::<Vec<_>>()
+            );
+            assert_eq!(
+                vec![(String::from(r"C:\dir\a"), &4)],
+                trie.subtree(r"C:\dir").collect::<Vec<_>>()
+            );
+            assert_eq!(vec![(String::from("~"), &5)], trie.subtree("~").collect::<Vec<_>>());
+            assert_eq!(vec![(String::from(r"~\a"), &6)], trie.subtree("~/").collect::<Vec<_>>());
+
+            for (path, value) in &trie {
+                assert_eq!(Some(value), trie.get(&path), "path='{path}'");
+            }
+            assert_eq!(6, trie.iter().count());
+        }
+
+        // the home root, with and without a following separator
+
+        {
+            let trie : PathTrie<i32, UnixSyntax> =
+                [("~", 1), ("~/a", 2), ("~/a/b", 3), ("/a", 4)].into_iter().collect();
+
+            assert_eq!(vec![(String::from("~"), &1)], trie.subtree("~").collect::<Vec<_>>());
+            assert_eq!(
+                vec![(String::from("~/a"), &2), (String::from("~/a/b"), &3)],
+                trie.subtree("~/a").collect::<Vec<_>>()
+            );
+            assert_eq!(
+                vec![
+                    (String::from("/a"), &4),
+                    (String::from("~"), &1),
+                    (String::from("~/a"), &2),
+                    (String::from("~/a/b"), &3)
+                ],
+                trie.iter().collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_PathTrie_remove() {
+        let mut trie : PathTrie<i32, UnixSyntax> = [("/a", 1), ("/a/b/c", 2), ("/a/d", 3)].into_iter().collect();
+
+        assert_eq!(None, trie.remove("/a/b"));
+        assert_eq!(Some(2), trie.remove("/a/b/c"));
+        assert_eq!(None, trie.remove("/a/b/c"));
+        assert_eq!(2, trie.len());
+
+        // the now-empty "b" node is pruned
+
+        assert_eq!(0, trie.subtree("/a/b").count());
+        assert_eq!(Some(("/a", &1)), trie.longest_prefix_match("/a/b/c"));
+
+        assert_eq!(Some(1), trie.remove("/a"));
+        assert_eq!(Some(&3), trie.get("/a/d"));
+        assert_eq!(Some(3), trie.remove("/a/d"));
+        assert!(trie.is_empty());
+        assert_eq!(0, trie.iter().count());
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -0,0 +1,202 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/syntax/unicode.rs
+ *
+ * Purpose: Unicode normalisation of the names of paths.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+use super::{
+    classification_flags,
+    path_classify,
+    PathSyntax,
+};
+
+use unicode_normalization::{
+    is_nfc,
+    is_nfd,
+    UnicodeNormalization,
+};
+
+use alloc::string::String;
+
+
+/// A Unicode normalisation form.
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+#[derive(Hash)]
+pub enum NormalForm {
+    /// Normalization Form C (canonical composition), as produced by most
+    /// Linux and Windows software.
+    Nfc,
+    /// Normalization Form D (canonical decomposition), as produced by
+    /// macOS for the names of files.
+    Nfd,
+}
+
+
+/// Applies the Unicode normal form `form` to each name in `path`,
+/// according to the syntax `S`.
+///
+/// The root (if any) and the separators are left untouched, so that the
+/// result classifies identically (other than in the lengths of its names)
+/// to `path`.
+///
+/// # Parameters:
+/// - `path` - the path;
+/// - `form` - the normal form to apply;
+pub fn normalise_unicode<S : PathSyntax>(
+    path : &str,
+    form : NormalForm,
+) -> String {
+    let root_len = root_length_::<S>(path);
+    let rest = &path[root_len..];
+
+    let mut result = String::with_capacity(path.len());
+    let mut name_start = 0;
+
+    let push_name = |result : &mut String, name : &str| {
+        match form {
+            NormalForm::Nfc => result.extend(name.nfc()),
+            NormalForm::Nfd => result.extend(name.nfd()),
+        }
+    };
+
+    result.push_str(&path[..root_len]);
+
+    for (index, c) in rest.char_indices() {
+        if S::char_is_path_name_separator(c) {
+            push_name(&mut result, &rest[name_start..index]);
+
+            result.push(c);
+
+            name_start = index + c.len_utf8();
+        }
+    }
+
+    push_name(&mut result, &rest[name_start..]);
+
+    result
+}
+
+/// Evaluates whether each name in `path` is already in the Unicode normal
+/// form `form`, according to the syntax `S`.
+///
+/// # Parameters:
+/// - `path` - the path;
+/// - `form` - the normal form;
+pub fn is_unicode_normalised<S : PathSyntax>(
+    path : &str,
+    form : NormalForm,
+) -> bool {
+    let root_len = root_length_::<S>(path);
+
+    path[root_len..].split(S::char_is_path_name_separator).all(|name| {
+        match form {
+            NormalForm::Nfc => is_nfc(name),
+            NormalForm::Nfd => is_nfd(name),
+        }
+    })
+}
+
+
+fn root_length_<S : PathSyntax>(path : &str) -> usize {
+    let (_, cr) = path_classify::<S>(path, classification_flags::IGNORE_INVALID_CHARS);
+
+    cr.Root.len()
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+    use crate::libpath::util::{
+        unix::UnixSyntax,
+        windows::WindowsSyntax,
+    };
+
+
+    const CAFE_NFC : &str = "caf\u{e9}";
+    const CAFE_NFD : &str = "cafe\u{301}";
+
+
+    #[test]
+    fn TEST_normalise_unicode() {
+        let nfc = format!("/dir/{CAFE_NFC}//{CAFE_NFC}.txt");
+        let nfd = format!("/dir/{CAFE_NFD}//{CAFE_NFD}.txt");
+
+        assert_eq!(nfc, normalise_unicode::<UnixSyntax>(&nfd, NormalForm::Nfc));
+        assert_eq!(nfd, normalise_unicode::<UnixSyntax>(&nfc, NormalForm::Nfd));
+        assert_eq!(nfc, normalise_unicode::<UnixSyntax>(&nfc, NormalForm::Nfc));
+
+        assert_eq!("", normalise_unicode::<UnixSyntax>("", NormalForm::Nfc));
+        assert_eq!("/", normalise_unicode::<UnixSyntax>("/", NormalForm::Nfd));
+    }
+
+    #[test]
+    fn TEST_normalise_unicode_LEAVES_ROOTS_AND_SEPARATORS_UNTOUCHED() {
+        let share_nfd = format!(r"\\server\{CAFE_NFD}");
+
+        let nfd = format!(r"{share_nfd}\dir/{CAFE_NFD}");
+        let expected = format!(r"{share_nfd}\dir/{CAFE_NFC}");
+
+        assert_eq!(expected, normalise_unicode::<WindowsSyntax>(&nfd, NormalForm::Nfc));
+    }
+
+    #[test]
+    fn TEST_is_unicode_normalised() {
+        let nfc = format!("dir/{CAFE_NFC}");
+        let nfd = format!("dir/{CAFE_NFD}");
+
+        assert!(is_unicode_normalised::<UnixSyntax>(&nfc, NormalForm::Nfc));
+        assert!(!is_unicode_normalised::<UnixSyntax>(&nfc, NormalForm::Nfd));
+        assert!(is_unicode_normalised::<UnixSyntax>(&nfd, NormalForm::Nfd));
+        assert!(!is_unicode_normalised::<UnixSyntax>(&nfd, NormalForm::Nfc));
+
+        assert!(is_unicode_normalised::<UnixSyntax>("dir/file.txt", NormalForm::Nfc));
+        assert!(is_unicode_normalised::<UnixSyntax>("dir/file.txt", NormalForm::Nfd));
+
+        let share_nfd = format!(r"\\server\{CAFE_NFD}\file");
+
+        assert!(is_unicode_normalised::<WindowsSyntax>(&share_nfd, NormalForm::Nfc));
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -0,0 +1,194 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/unix/file_uri.rs
+ *
+ * Purpose: Conversion of UNIX paths to, and from, file URIs.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+use super::{
+    path_classify,
+    Classification,
+};
+use crate::libpath::util::common::{
+    file_uri::{
+        percent_decode_,
+        percent_encode_into_,
+        split_file_uri_,
+    },
+    FileUriError,
+};
+
+use alloc::string::String;
+
+
+/// Obtains the `file:` URI of an absolute UNIX path, e.g.
+/// `"file:///dir/my%20file.ext"` for `"/dir/my file.ext"`.
+///
+/// # Errors:
+/// - [`FileUriError::NotAbsolute`] if `path` is not slash-rooted;
+/// - [`FileUriError::InvalidChars`] if `path` contains invalid characters;
+pub fn to_file_uri(path : &str) -> Result<String, FileUriError> {
+    let (cl, _) = path_classify(path, 0);
+
+    match cl {
+        Classification::SlashRooted => (),
+        Classification::InvalidChars => return Err(FileUriError::InvalidChars),
+        _ => return Err(FileUriError::NotAbsolute),
+    }
+
+    let mut uri = String::with_capacity(7 + path.len());
+
+    uri.push_str("file://");
+    percent_encode_into_(&mut uri, path);
+
+    Ok(uri)
+}
+
+/// Obtains the UNIX path of a `file:` URI, e.g. `"/dir/my file.ext"` for
+/// `"file:///dir/my%20file.ext"`.
+///
+/// URIs with an empty or `localhost` host, or - as permitted by RFC 8089 -
+/// with no authority at all (e.g. `"file:/dir"`), are accepted. Any query
+/// or fragment is ignored.
+///
+/// # Errors:
+/// - [`FileUriError::NotFileUri`] if `uri` is not a `file:` URI;
+/// - [`FileUriError::UnsupportedHost`] if `uri` names any other host;
+/// - [`FileUriError::InvalidPercentEncoding`] or
+///   [`FileUriError::NotUnicode`] if `uri` cannot be decoded;
+/// - [`FileUriError::NotAbsolute`] or [`FileUriError::InvalidChars`] if
+///   the decoded path is not classified as
+///   [`SlashRooted`](Classification::SlashRooted);
+pub fn from_file_uri(uri : &str) -> Result<String, FileUriError> {
+    let (authority, encoded_path) = split_file_uri_(uri)?;
+
+    match authority {
+        None | Some("") => (),
+        Some(host) if host.eq_ignore_ascii_case("localhost") => (),
+        Some(_) => return Err(FileUriError::UnsupportedHost),
+    }
+
+    let path = percent_decode_(encoded_path)?;
+
+    let (cl, _) = path_classify(&path, 0);
+
+    match cl {
+        Classification::SlashRooted => Ok(path),
+        Classification::InvalidChars => Err(FileUriError::InvalidChars),
+        _ => Err(FileUriError::NotAbsolute),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+
+    #[test]
+    fn TEST_to_file_uri() {
+        assert_eq!(Ok(String::from("file:///dir/file.ext")), to_file_uri("/dir/file.ext"));
+        assert_eq!(
+            Ok(String::from("file:///dir/my%20file%231.ext")),
+            to_file_uri("/dir/my file#1.ext")
+        );
+        assert_eq!(Ok(String::from("file:///caf%C3%A9")), to_file_uri("/caf\u{E9}"));
+        assert_eq!(Ok(String::from("file:///")), to_file_uri("/"));
+
+        assert_eq!(Err(FileUriError::NotAbsolute), to_file_uri("dir/file.ext"));
+        assert_eq!(Err(FileUriError::NotAbsolute), to_file_uri("~/file.ext"));
+        assert_eq!(Err(FileUriError::NotAbsolute), to_file_uri(""));
+        assert_eq!(Err(FileUriError::InvalidChars), to_file_uri("/dir/fi\0le"));
+    }
+
+    #[test]
+    fn TEST_from_file_uri() {
+        assert_eq!(Ok(String::from("/dir/file.ext")), from_file_uri("file:///dir/file.ext"));
+        assert_eq!(
+            Ok(String::from("/dir/file.ext")),
+            from_file_uri("file://localhost/dir/file.ext")
+        );
+        assert_eq!(Ok(String::from("/dir/file.ext")), from_file_uri("file:/dir/file.ext"));
+        assert_eq!(
+            Ok(String::from("/dir/my file#1.ext")),
+            from_file_uri("file:///dir/my%20file%231.ext")
+        );
+        assert_eq!(
+            Ok(String::from("/dir/file.ext")),
+            from_file_uri("file:///dir/file.ext#L10")
+        );
+
+        assert_eq!(
+            Err(FileUriError::NotFileUri),
+            from_file_uri("https://host/dir/file.ext")
+        );
+        assert_eq!(
+            Err(FileUriError::UnsupportedHost),
+            from_file_uri("file://server/share/file.ext")
+        );
+        assert_eq!(
+            Err(FileUriError::InvalidPercentEncoding),
+            from_file_uri("file:///dir/%G0")
+        );
+        assert_eq!(Err(FileUriError::NotUnicode), from_file_uri("file:///dir/%FF"));
+        assert_eq!(Err(FileUriError::InvalidChars), from_file_uri("file:///dir/fi%00le"));
+        assert_eq!(Err(FileUriError::NotAbsolute), from_file_uri("file:dir/file.ext"));
+    }
+
+    #[test]
+    fn TEST_ROUNDTRIP() {
+        for path in [
+            "/dir/file.ext",
+            "/dir/my file%20.ext",
+            "/a?b/#c",
+            "/caf\u{E9}/\u{1F600}",
+        ] {
+            let uri = to_file_uri(path).unwrap();
+
+            assert_eq!(
+                Ok(String::from(path)),
+                from_file_uri(&uri),
+                "path='{path}', uri='{uri}'"
+            );
+        }
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -0,0 +1,371 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/windows/drive_directories.rs
+ *
+ * Purpose: Definition of DriveDirectories, which resolves drive-relative Windows
+ *          paths.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+use super::{
+    classification_flags,
+    path_classify,
+    Classification,
+};
+use crate::libpath::util::common::ClassificationResult;
+
+use alloc::{
+    borrow::ToOwned,
+    string::String,
+};
+use core::fmt;
+
+
+/// An error in resolving a drive-relative path.
+#[derive(Clone, Copy)]
+#[derive(Debug)]
+#[derive(PartialEq, Eq)]
+pub enum DriveResolutionError {
+    /// There is no current directory for the drive, which is given in
+    /// upper-case.
+    NoCurrentDirectory(char),
+    /// The path is neither drive-relative nor absolute, e.g. `dir\file` or
+    /// `\dir\file`.
+    NotDriveQualified,
+    /// The directory is not an absolute path on the given drive.
+    InvalidDirectory,
+}
+
+impl fmt::Display for DriveResolutionError {
+    fn fmt(
+        &self,
+        f : &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::NoCurrentDirectory(drive) => write!(f, "no current directory for drive {drive}:"),
+            Self::NotDriveQualified => f.write_str("path does not specify a drive"),
+            Self::InvalidDirectory => f.write_str("directory is not an absolute path on the drive"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DriveResolutionError {
+}
+
+
+/// A table of the current directory of each drive, as is maintained by
+/// the Windows command processor in the hidden `=C:`, `=D:`, ...,
+/// environment variables, with which drive-relative paths such as
+/// `D:dir\file` may be resolved.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(PartialEq, Eq)]
+pub struct DriveDirectories {
+    directories : [Option<String>; 26],
+}
+
+// API functions
+impl DriveDirectories {
+    /// Creates an empty instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an instance from the `=C:`-style variables among the given
+    /// environment variables, ignoring all others and any whose values are
+    /// not absolute paths on their drives.
+    ///
+    /// # Parameters:
+    /// - `variables` - the name/value pairs of the environment variables;
+    pub fn from_variables<I, K, V>(variables : I) -> Self
+    where
+        I : IntoIterator<Item = (K, V)>,
+        K : AsRef<str>,
+        V : AsRef<str>,
+    {
+        let mut drive_directories = Self::new();
+
+        for (name, value) in variables {
+            if let [b'=', drive, b':'] = name.as_ref().as_bytes() {
+                let _ = drive_directories.set(*drive as char, value.as_ref());
+            }
+        }
+
+        drive_directories
+    }
+
+    /// Creates an instance from the `=C:`-style variables in the
+    /// environment of the current process.
+    ///
+    /// # Note:
+    /// Such variables only exist on Windows, so elsewhere the instance will
+    /// (usually) be empty.
+    #[cfg(feature = "std")]
+    pub fn from_env() -> Self {
+        Self::from_variables(
+            std::env::vars_os().filter_map(|(k, v)| Some((k.into_string().ok()?, v.into_string().ok()?))),
+        )
+    }
+}
+
+// Mutating methods
+impl DriveDirectories {
+    /// Sets the current directory of a drive.
+    ///
+    /// # Parameters:
+    /// - `drive` - the drive letter, in either case;
+    /// - `directory` - the current directory, which must be an absolute
+    ///   path on `drive`, e.g. `"D:\work"`;
+    ///
+    /// # Errors:
+    /// [`DriveResolutionError::InvalidDirectory`] if `drive` is not a letter
+    /// or `directory` is not an absolute path on `drive`.
+    pub fn set(
+        &mut self,
+        drive : char,
+        directory : &str,
+    ) -> Result<(), DriveResolutionError> {
+        let index = drive_index_(drive).ok_or(DriveResolutionError::InvalidDirectory)?;
+
+        let (cl, cr) = path_classify(directory, 0);
+
+        if Classification::DriveLetterRooted != cl || drive_index_(root_drive_(directory, &cr)) != Some(index) {
+            return Err(DriveResolutionError::InvalidDirectory);
+        }
+
+        self.directories[index] = Some(directory.to_owned());
+
+        Ok(())
+    }
+
+    /// Removes the current directory of a drive, if any.
+    pub fn remove(
+        &mut self,
+        drive : char,
+    ) -> Option<String> {
+        drive_index_(drive).and_then(|index| self.directories[index].take())
+    }
+}
+
+// Non-mutating methods
+impl DriveDirectories {
+    /// Obtains the current directory of a drive, if any.
+    pub fn get(
+        &self,
+        drive : char,
+    ) -> Option<&str> {
+        drive_index_(drive).and_then(|index| self.directories[index].as_deref())
+    }
+
+    /// Resolves a drive-relative path, such as `D:dir\file`, into an
+    /// absolute path, such as `D:\work\dir\file`, by combining it with the
+    /// current directory of its drive.
+    ///
+    /// Paths that are already absolute - rooted at a drive, a UNC share,
+    /// or a device - are returned as they are.
+    ///
+    /// Any `\\?\` prefix of `path` is retained, e.g. `\\?\D:dir` is
+    /// resolved to `\\?\D:\work\dir`, unless the current directory
+    /// has one of its own. Characters that are invalid in Windows paths are
+    /// copied as they are, since the resolution is purely lexical.
+    ///
+    /// # Errors:
+    /// - [`DriveResolutionError::NoCurrentDirectory`] if there is no
+    ///   current directory for the drive of `path`;
+    /// - [`DriveResolutionError::NotDriveQualified`] if `path` is neither
+    ///   drive-relative nor absolute;
+    pub fn resolve(
+        &self,
+        path : &str,
+    ) -> Result<String, DriveResolutionError> {
+        let (cl, cr) = path_classify(path, classification_flags::IGNORE_INVALID_CHARS);
+
+        match cl {
+            Classification::DriveLetterRelative => (),
+            Classification::DriveLetterRooted
+            | Classification::UncRooted
+            | Classification::WslRooted
+            | Classification::DeviceRooted => return Ok(path.to_owned()),
+            _ => return Err(DriveResolutionError::NotDriveQualified),
+        }
+
+        let drive = root_drive_(path, &cr);
+
+        let directory = self
+            .get(drive)
+            .ok_or(DriveResolutionError::NoCurrentDirectory(drive.to_ascii_uppercase()))?;
+
+        let prefix = cr.Prefix.substring_of(path);
+        let rest = &path[cr.Root.offset + cr.Root.len()..];
+
+        let mut resolved = String::with_capacity(prefix.len() + directory.len() + 1 + rest.len());
+
+        if !directory.starts_with(prefix) {
+            resolved.push_str(prefix);
+        }
+        resolved.push_str(directory);
+
+        if !rest.is_empty() {
+            if !directory.ends_with(['\\', '/']) {
+                resolved.push('\\');
+            }
+
+            resolved.push_str(rest);
+        }
+
+        Ok(resolved)
+    }
+}
+
+
+/// Obtains the drive letter of a path that is classified as drive rooted
+/// or drive relative.
+fn root_drive_(
+    path : &str,
+    cr : &ClassificationResult,
+) -> char {
+    let root = cr.Root.substring_of(path);
+
+    root[cr.Prefix.len()..].chars().next().unwrap_or('\0')
+}
+
+/// Obtains the index, 0-25, of a drive letter.
+fn drive_index_(drive : char) -> Option<usize> {
+    if drive.is_ascii_alphabetic() {
+        Some(usize::from(drive.to_ascii_uppercase() as u8 - b'A'))
+    } else {
+        None
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+
+    #[test]
+    fn TEST_DriveDirectories_set_AND_get() {
+        let mut dd = DriveDirectories::new();
+
+        assert_eq!(None, dd.get('C'));
+
+        assert_eq!(Ok(()), dd.set('c', r"C:\Users\me"));
+        assert_eq!(Some(r"C:\Users\me"), dd.get('C'));
+        assert_eq!(Some(r"C:\Users\me"), dd.get('c'));
+
+        assert_eq!(Err(DriveResolutionError::InvalidDirectory), dd.set('D', r"C:\Users"));
+        assert_eq!(Err(DriveResolutionError::InvalidDirectory), dd.set('D', r"D:Users"));
+        assert_eq!(Err(DriveResolutionError::InvalidDirectory), dd.set('D', r"\Users"));
+        assert_eq!(Err(DriveResolutionError::InvalidDirectory), dd.set('1', r"C:\Users"));
+
+        assert_eq!(Some(String::from(r"C:\Users\me")), dd.remove('C'));
+        assert_eq!(None, dd.get('C'));
+    }
+
+    #[test]
+    fn TEST_DriveDirectories_from_variables() {
+        let dd = DriveDirectories::from_variables([
+            ("=C:", r"C:\Windows"),
+            ("=D:", r"D:\work"),
+            ("=E:", r"C:\mismatched"),
+            ("=ExitCode", "00000000"),
+            ("PATH", r"C:\Windows"),
+        ]);
+
+        assert_eq!(Some(r"C:\Windows"), dd.get('C'));
+        assert_eq!(Some(r"D:\work"), dd.get('D'));
+        assert_eq!(None, dd.get('E'));
+    }
+
+    #[test]
+    fn TEST_DriveDirectories_resolve() {
+        let dd = DriveDirectories::from_variables([("=C:", r"C:\"), ("=D:", r"D:\work")]);
+
+        let cases = [
+            (r"D:foo\bar", r"D:\work\foo\bar"),
+            (r"d:foo\bar", r"D:\work\foo\bar"),
+            (r"D:", r"D:\work"),
+            (r"D:..\other", r"D:\work\..\other"),
+            (r"C:foo", r"C:\foo"),
+            (r"E:\abs\path", r"E:\abs\path"),
+            (r"\\server\share\file", r"\\server\share\file"),
+            (r"D:a?b", r"D:\work\a?b"),
+            (r"D:dir\a|b", r"D:\work\dir\a|b"),
+            (r"\\?\D:foo\bar", r"\\?\D:\work\foo\bar"),
+            (r"\\?\D:", r"\\?\D:\work"),
+            (r"\\?\C:foo", r"\\?\C:\foo"),
+            (r"\\?\E:\abs\path", r"\\?\E:\abs\path"),
+        ];
+
+        for (path, expected) in cases {
+            assert_eq!(Ok(String::from(expected)), dd.resolve(path), "path='{path}'");
+        }
+    }
+
+    #[test]
+    fn TEST_DriveDirectories_resolve_WITH_LONG_PATH_DIRECTORY() {
+        let mut dd = DriveDirectories::new();
+
+        assert_eq!(Ok(()), dd.set('D', r"\\?\D:\work"));
+
+        assert_eq!(Ok(String::from(r"\\?\D:\work\foo")), dd.resolve(r"\\?\D:foo"));
+        assert_eq!(Ok(String::from(r"\\?\D:\work\foo")), dd.resolve(r"D:foo"));
+    }
+
+    #[test]
+    fn TEST_DriveDirectories_resolve_WITH_ERRORS() {
+        let dd = DriveDirectories::from_variables([("=C:", r"C:\")]);
+
+        assert_eq!(Err(DriveResolutionError::NoCurrentDirectory('E')), dd.resolve(r"e:foo"));
+        assert_eq!(Err(DriveResolutionError::NotDriveQualified), dd.resolve(r"foo\bar"));
+        assert_eq!(Err(DriveResolutionError::NotDriveQualified), dd.resolve(r"\foo\bar"));
+        assert_eq!(Err(DriveResolutionError::NotDriveQualified), dd.resolve(r"foo\a?b"));
+        assert_eq!(Err(DriveResolutionError::NoCurrentDirectory('E')), dd.resolve(r"E:a?b"));
+
+        assert_eq!(
+            "no current directory for drive E:",
+            DriveResolutionError::NoCurrentDirectory('E').to_string()
+        );
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
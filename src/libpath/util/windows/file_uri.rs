@@ -0,0 +1,307 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    src/libpath/util/windows/file_uri.rs
+ *
+ * Purpose: Conversion of Windows paths to, and from, file URIs.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+use super::{
+    path_classify,
+    Classification,
+    WindowsSyntax,
+};
+use crate::libpath::util::{
+    common::{
+        file_uri::{
+            percent_decode_,
+            percent_encode_into_,
+            split_file_uri_,
+        },
+        FileUriError,
+    },
+    syntax::PathSyntax,
+};
+
+use alloc::string::String;
+
+
+/// Obtains the `file:` URI of an absolute Windows path, e.g.
+/// `"file:///C:/dir/my%20file.ext"` for `"C:\dir\my file.ext"`, and
+/// `"file://server/share/file.ext"` for `"\\server\share\file.ext"`.
+///
+/// Any `\\?\` prefix is discarded.
+///
+/// # Errors:
+/// - [`FileUriError::NotAbsolute`] if `path` is not rooted at a drive or
+///   at a UNC share;
+/// - [`FileUriError::InvalidChars`] if `path` contains invalid characters;
+pub fn to_file_uri(path : &str) -> Result<String, FileUriError> {
+    let (cl, cr) = path_classify(path, 0);
+
+    let unprefixed = &path[cr.Prefix.len()..];
+    let rest = &path[cr.Root.offset + cr.Root.len()..].replace('\\', "/");
+
+    let mut uri = String::with_capacity(8 + path.len());
+
+    uri.push_str("file://");
+
+    match cl {
+        Classification::DriveLetterRooted => {
+            uri.push('/');
+            uri.push_str(&unprefixed[..2]);
+        },
+        Classification::UncRooted | Classification::WslRooted => {
+            // the server and share names, with the leading separators (or
+            // the "\\?\UNC\" prefix) removed
+
+            let root = &cr.Root.substring_of(path)[cr.Prefix.len()..];
+            let names = if cr.Prefix.is_empty() { &root[2..] } else { root }.replace('\\', "/");
+
+            percent_encode_into_(&mut uri, &names);
+        },
+        Classification::InvalidChars => return Err(FileUriError::InvalidChars),
+        _ => return Err(FileUriError::NotAbsolute),
+    }
+
+    percent_encode_into_(&mut uri, rest);
+
+    Ok(uri)
+}
+
+/// Obtains the Windows path of a `file:` URI, e.g. `"C:\dir\my file.ext"`
+/// for `"file:///C:/dir/my%20file.ext"`, and `"\\server\share\file.ext"`
+/// for `"file://server/share/file.ext"`.
+///
+/// The legacy drive form `"file:///C|/dir"`, and the legacy UNC form
+/// `"file:////server/share"`, are also accepted. Any query or fragment is
+/// ignored.
+///
+/// # Errors:
+/// - [`FileUriError::NotFileUri`] if `uri` is not a `file:` URI;
+/// - [`FileUriError::InvalidPercentEncoding`] or
+///   [`FileUriError::NotUnicode`] if `uri` cannot be decoded;
+/// - [`FileUriError::UnsupportedHost`] if the decoded host is not a valid
+///   server name - e.g. `"file://ser%2Fver/share"`, which would otherwise
+///   yield the server `"ser"` and the share `"ver"`;
+/// - [`FileUriError::NotAbsolute`] or [`FileUriError::InvalidChars`] if
+///   the decoded path is not classified as rooted at a drive or at a UNC
+///   share;
+pub fn from_file_uri(uri : &str) -> Result<String, FileUriError> {
+    let (authority, encoded_path) = split_file_uri_(uri)?;
+
+    let mut path = String::with_capacity(2 + encoded_path.len());
+
+    match authority {
+        Some(host) if !host.is_empty() && !host.eq_ignore_ascii_case("localhost") => {
+            push_unc_(&mut path, host, encoded_path)?;
+        },
+        // "file:////server/share" (legacy UNC form)
+        _ if encoded_path.starts_with("//") => {
+            let (host, encoded_path) = match encoded_path[2..].find('/') {
+                Some(index) => encoded_path[2..].split_at(index),
+                None => (&encoded_path[2..], ""),
+            };
+
+            push_unc_(&mut path, host, encoded_path)?;
+        },
+        _ => {
+            let decoded = percent_decode_(encoded_path)?;
+
+            // "/C:/dir" and "/C|/dir" => "C:/dir"
+
+            match decoded.as_bytes() {
+                [b'/', drive, b':' | b'|', ..] if drive.is_ascii_alphabetic() => {
+                    path.push(*drive as char);
+                    path.push(':');
+                    path.push_str(&decoded[3..]);
+                },
+                _ => path.push_str(&decoded),
+            }
+        },
+    }
+
+    let path = path.replace('/', "\\");
+
+    let (cl, _) = path_classify(&path, 0);
+
+    match cl {
+        Classification::DriveLetterRooted | Classification::UncRooted | Classification::WslRooted => Ok(path),
+        Classification::InvalidChars => Err(FileUriError::InvalidChars),
+        _ => Err(FileUriError::NotAbsolute),
+    }
+}
+
+/// Appends to `path` the UNC path of the (encoded) host and path,
+/// validating the decoded host as a server name.
+fn push_unc_(
+    path : &mut String,
+    encoded_host : &str,
+    encoded_path : &str,
+) -> Result<(), FileUriError> {
+    let host = percent_decode_(encoded_host)?;
+
+    if !host_is_valid_server_name_(&host) {
+        return Err(FileUriError::UnsupportedHost);
+    }
+
+    path.push_str(r"\\");
+    path.push_str(&host);
+    path.push_str(&percent_decode_(encoded_path)?);
+
+    Ok(())
+}
+
+/// Indicates whether `host` may be the server name of a UNC path, which
+/// requires that it contain neither a path-name separator nor an invalid
+/// character, and that it not be `"."` or `"?"`, which would instead form
+/// a device (`\\.\`) or long-path (`\\?\`) prefix.
+fn host_is_valid_server_name_(host : &str) -> bool {
+    !host.is_empty()
+        && "." != host
+        && "?" != host
+        && !host
+            .chars()
+            .any(|c| WindowsSyntax::char_is_path_name_separator(c) || WindowsSyntax::char_is_invalid(c))
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+
+    #[test]
+    fn TEST_to_file_uri() {
+        let cases = [
+            (r"C:\dir\file.ext", "file:///C:/dir/file.ext"),
+            (r"c:/dir/my file#1.ext", "file:///c:/dir/my%20file%231.ext"),
+            (r"C:\", "file:///C:/"),
+            (r"\\server\share\dir\file.ext", "file://server/share/dir/file.ext"),
+            (r"\\server\share", "file://server/share"),
+            (r"\\?\C:\dir\file.ext", "file:///C:/dir/file.ext"),
+            (r"\\?\UNC\server\share\file.ext", "file://server/share/file.ext"),
+            (r"\\wsl$\Ubuntu\home", "file://wsl%24/Ubuntu/home"),
+            ("C:\\caf\u{E9}", "file:///C:/caf%C3%A9"),
+        ];
+
+        for (path, expected) in cases {
+            assert_eq!(Ok(String::from(expected)), to_file_uri(path), "path='{path}'");
+        }
+    }
+
+    #[test]
+    fn TEST_to_file_uri_WITH_UNREPRESENTABLE_PATHS() {
+        let cases = [
+            (r"dir\file.ext", FileUriError::NotAbsolute),
+            (r"C:dir\file.ext", FileUriError::NotAbsolute),
+            (r"\dir\file.ext", FileUriError::NotAbsolute),
+            (r"\\server", FileUriError::NotAbsolute),
+            (r"\\.\COM1", FileUriError::NotAbsolute),
+            (r"C:\dir\what?", FileUriError::InvalidChars),
+        ];
+
+        for (path, expected) in cases {
+            assert_eq!(Err(expected), to_file_uri(path), "path='{path}'");
+        }
+    }
+
+    #[test]
+    fn TEST_from_file_uri() {
+        let cases = [
+            ("file:///C:/dir/file.ext", r"C:\dir\file.ext"),
+            ("file://localhost/C:/dir/file.ext", r"C:\dir\file.ext"),
+            ("file:/C:/dir/file.ext", r"C:\dir\file.ext"),
+            ("file:///C|/dir/file.ext", r"C:\dir\file.ext"),
+            ("file:///c:/dir/my%20file%231.ext", r"c:\dir\my file#1.ext"),
+            ("file://server/share/dir/file.ext", r"\\server\share\dir\file.ext"),
+            ("file:////server/share/dir/file.ext", r"\\server\share\dir\file.ext"),
+            ("file://wsl%24/Ubuntu/home", r"\\wsl$\Ubuntu\home"),
+            ("file:///C:/dir/file.ext?query#fragment", r"C:\dir\file.ext"),
+        ];
+
+        for (uri, expected) in cases {
+            assert_eq!(Ok(String::from(expected)), from_file_uri(uri), "uri='{uri}'");
+        }
+    }
+
+    #[test]
+    fn TEST_from_file_uri_WITH_INVALID_URIS() {
+        let cases = [
+            ("https://server/share", FileUriError::NotFileUri),
+            ("file:///C:/dir/%G0", FileUriError::InvalidPercentEncoding),
+            ("file:///C:/dir/%FF", FileUriError::NotUnicode),
+            ("file:///dir/file.ext", FileUriError::NotAbsolute),
+            ("file://server", FileUriError::NotAbsolute),
+            ("file:///C:/dir/what%3F", FileUriError::InvalidChars),
+            ("file://ser%2Fver/share/x", FileUriError::UnsupportedHost),
+            ("file://ser%5Cver/share/x", FileUriError::UnsupportedHost),
+            ("file://ser%3Aver/share/x", FileUriError::UnsupportedHost),
+            ("file://%2E/COM1", FileUriError::UnsupportedHost),
+            ("file://%3F/C:/dir", FileUriError::UnsupportedHost),
+            ("file:////ser%2Fver/share/x", FileUriError::UnsupportedHost),
+            ("file:////ser%5Cver/share/x", FileUriError::UnsupportedHost),
+            ("file:////./COM1", FileUriError::UnsupportedHost),
+        ];
+
+        for (uri, expected) in cases {
+            assert_eq!(Err(expected), from_file_uri(uri), "uri='{uri}'");
+        }
+    }
+
+    #[test]
+    fn TEST_ROUNDTRIP() {
+        for path in [
+            r"C:\dir\file.ext",
+            r"D:\my dir\100%.ext",
+            r"\\server\share\file.ext",
+            "C:\\caf\u{E9}",
+        ] {
+            let uri = to_file_uri(path).unwrap();
+
+            assert_eq!(
+                Ok(String::from(path)),
+                from_file_uri(&uri),
+                "path='{path}', uri='{uri}'"
+            );
+        }
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -0,0 +1,391 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    tests/cli.rs
+ *
+ * Purpose: Integration tests of the libpath command-line tool, covering the
+ *          human and JSON output of each subcommand and its exit codes.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+#![allow(non_snake_case)]
+
+use serde_json::Value;
+
+use std::{
+    io::Write,
+    process::{
+        Command,
+        Output,
+        Stdio,
+    },
+};
+
+
+/// Runs the tool with the given arguments, returning its output.
+fn run_(args : &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_libpath"))
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .expect("could not run libpath")
+}
+
+/// Runs the tool with the given arguments and standard input, returning
+/// its output.
+fn run_with_stdin_(
+    args : &[&str],
+    stdin : &str,
+) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_libpath"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("could not run libpath");
+
+    child.stdin.take().unwrap().write_all(stdin.as_bytes()).unwrap();
+
+    child.wait_with_output().unwrap()
+}
+
+fn stdout_(output : &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr_(output : &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+fn json_(output : &Output) -> Vec<Value> {
+    match serde_json::from_slice(&output.stdout).unwrap() {
+        Value::Array(values) => values,
+        value => panic!("expected an array, but got {value}"),
+    }
+}
+
+
+#[test]
+fn TEST_classify_HUMAN() {
+    let output = run_(&["classify", "/usr/lib", "--syntax", "unix"]);
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("/usr/lib: SlashRooted (unix)\n", stdout_(&output));
+}
+
+#[test]
+fn TEST_classify_JSON() {
+    let output = run_(&["classify", "--format", "json", r"C:\dir\file.txt"]);
+
+    assert_eq!(Some(0), output.status.code());
+
+    let values = json_(&output);
+
+    assert_eq!(1, values.len());
+    assert_eq!(r"C:\dir\file.txt", values[0]["path"]);
+    assert_eq!("windows", values[0]["syntax"]);
+    assert_eq!("DriveLetterRooted", values[0]["classification"]);
+    assert_eq!(7, values[0]["classification_result"]["EntryName"]["offset"]);
+    assert_eq!(8, values[0]["classification_result"]["EntryName"]["length"]);
+    assert_eq!(2, values[0]["classification_result"]["NumDirectoryParts"]);
+}
+
+#[test]
+fn TEST_classify_FROM_STDIN() {
+    let output = run_with_stdin_(&["classify", "--syntax", "unix"], "/a\nb\n");
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("/a: SlashRooted (unix)\nb: Relative (unix)\n", stdout_(&output));
+}
+
+#[test]
+fn TEST_normalize_HUMAN() {
+    let output = run_(&["normalize", "a/./b/../c", "/x//y/"]);
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("a/c\n/x/y\n", stdout_(&output));
+}
+
+#[test]
+fn TEST_normalize_JSON() {
+    let output = run_(&["normalise", "--format", "json", "a/./b"]);
+
+    assert_eq!(Some(0), output.status.code());
+
+    let values = json_(&output);
+
+    assert_eq!(1, values.len());
+    assert_eq!("a/./b", values[0]["path"]);
+    assert_eq!("unix", values[0]["syntax"]);
+    assert_eq!("a/b", values[0]["result"]);
+}
+
+#[test]
+fn TEST_relative_HUMAN() {
+    let output = run_(&["relative", "/a/b", "/a/c/d"]);
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("../c/d\n", stdout_(&output));
+}
+
+#[test]
+fn TEST_relative_JSON() {
+    let output = run_(&["relative", "--format", "json", "/a/b", "/a/c/d"]);
+
+    assert_eq!(Some(0), output.status.code());
+
+    let values = json_(&output);
+
+    assert_eq!("/a/c/d", values[0]["path"]);
+    assert_eq!("/a/b", values[0]["base"]);
+    assert_eq!("../c/d", values[0]["result"]);
+}
+
+#[test]
+fn TEST_relative_WITH_DIFFERENT_ROOTS_FAILS() {
+    let output = run_(&["relative", "--syntax", "windows", r"C:\a", r"D:\b"]);
+
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!("", stdout_(&output));
+    assert!(stderr_(&output).starts_with(r"libpath: D:\b: "));
+
+    let output = run_(&["relative", "--syntax", "windows", "--format", "json", r"C:\a", r"D:\b"]);
+
+    assert_eq!(Some(1), output.status.code());
+
+    let values = json_(&output);
+
+    assert_eq!(r"D:\b", values[0]["path"]);
+    assert_eq!(r"C:\a", values[0]["base"]);
+    assert!(values[0]["error"].is_string());
+    assert!(values[0].get("result").is_none());
+}
+
+#[test]
+fn TEST_join_HUMAN() {
+    let output = run_(&["join", "/a", "b", "c"]);
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("/a/b/c\n", stdout_(&output));
+}
+
+#[test]
+fn TEST_join_JSON() {
+    let output = run_(&["join", "--format", "json", "/a", "b", "/c"]);
+
+    assert_eq!(Some(0), output.status.code());
+
+    let values = json_(&output);
+
+    assert_eq!(1, values.len());
+    assert_eq!("unix", values[0]["syntax"]);
+    assert_eq!("/c", values[0]["result"]);
+}
+
+#[test]
+fn TEST_convert_HUMAN() {
+    let output = run_(&["convert", "--to", "windows", "/c/Users/me"]);
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("C:\\Users\\me\n", stdout_(&output));
+
+    let output = run_(&["convert", "--to", "unix", "--mapping", "wsl", r"C:\Users\me"]);
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("/mnt/c/Users/me\n", stdout_(&output));
+}
+
+#[test]
+fn TEST_convert_JSON() {
+    let output = run_(&["convert", "--format", "json", "--to", "unix", r"C:\Users\me"]);
+
+    assert_eq!(Some(0), output.status.code());
+
+    let values = json_(&output);
+
+    assert_eq!(r"C:\Users\me", values[0]["path"]);
+    assert_eq!("windows", values[0]["syntax"]);
+    assert_eq!("/c/Users/me", values[0]["result"]);
+}
+
+#[test]
+fn TEST_check_HUMAN() {
+    let output = run_(&["check", "a/b"]);
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("a/b: portable\n", stdout_(&output));
+
+    let output = run_(&["check", "a:b"]);
+
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!(
+        "a:b: not portable\n  0: root is not portable (\"a:\")\n",
+        stdout_(&output)
+    );
+}
+
+#[test]
+fn TEST_check_JSON() {
+    let output = run_(&["check", "--format", "json", "a/b", "dir/CON.txt"]);
+
+    assert_eq!(Some(1), output.status.code());
+
+    let values = json_(&output);
+
+    assert_eq!(2, values.len());
+    assert_eq!(true, values[0]["portable"]);
+    assert_eq!(0, values[0]["problems"].as_array().unwrap().len());
+    assert_eq!(false, values[1]["portable"]);
+    assert_eq!("ReservedName", values[1]["problems"][0]["kind"]);
+    assert_eq!(4, values[1]["problems"][0]["offset"]);
+    assert_eq!("CON.txt", values[1]["problems"][0]["text"]);
+}
+
+#[test]
+fn TEST_check_WITH_SYNTAX() {
+    let output = run_(&["check", "--syntax", "auto", "a/b"]);
+
+    assert_eq!(Some(0), output.status.code());
+
+    // "a:b" has a drive root on Windows, but not on UNIX, where ':' is
+    // merely not in the POSIX portable filename character set
+
+    let output = run_(&["check", "a:b", "--syntax", "unix"]);
+
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!(
+        "a:b: not portable\n  1: character ':' is not in the POSIX portable filename character set (\":\")\n",
+        stdout_(&output)
+    );
+
+    let output = run_(&["check", "--syntax", "windows", "a:b"]);
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("a:b: portable\n", stdout_(&output));
+
+    // reserved names are a problem only on Windows
+
+    let output = run_(&["check", "--syntax", "unix", "dir/CON.txt"]);
+
+    assert_eq!(Some(0), output.status.code());
+
+    let output = run_(&["check", "--syntax", "windows", "--format", "json", "dir/CON.txt"]);
+
+    assert_eq!(Some(1), output.status.code());
+    assert_eq!("ReservedName", json_(&output)[0]["problems"][0]["kind"]);
+}
+
+#[test]
+fn TEST_CLASSIFICATION_FLAGS_ARE_A_USAGE_ERROR_OTHER_THAN_FOR_classify_AND_expand() {
+    for args in [
+        &["check", "--ignore-slash-runs", "a//b"][..],
+        &["check", "--recognise-wsl-drives", "/mnt/c/x"][..],
+        &["normalize", "--ignore-invalid-chars", "a/b"][..],
+        &["relative", "--recognise-tilde-home", "/a", "/a/b"][..],
+        &["join", "--ignore-slash-runs", "/a", "b"][..],
+        &["convert", "--to", "windows", "--recognise-msys-drives", "/c/x"][..],
+    ] {
+        let output = run_(args);
+
+        assert_eq!(Some(2), output.status.code(), "{args:?}");
+        assert_eq!("", stdout_(&output), "{args:?}");
+    }
+}
+
+#[test]
+fn TEST_classify_WITH_CLASSIFICATION_FLAGS() {
+    let output = run_(&["classify", "--syntax", "windows", "a?b"]);
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("a?b: InvalidChars (windows)\n", stdout_(&output));
+
+    let output = run_(&["classify", "--syntax", "windows", "--ignore-invalid-chars", "a?b"]);
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("a?b: Relative (windows)\n", stdout_(&output));
+
+    let output = run_(&["expand", "--recognise-wsl-drives", "--format", "json", "/mnt/c/x"]);
+
+    assert_eq!(Some(0), output.status.code());
+    assert_eq!("ForeignDriveRooted", json_(&output)[0]["classification"]);
+}
+
+#[test]
+fn TEST_expand_HUMAN() {
+    let output = run_(&["expand", "a/b.txt"]);
+
+    assert_eq!(Some(0), output.status.code());
+
+    let stdout = stdout_(&output);
+
+    assert!(stdout.starts_with("a/b.txt: Relative (unix)\n"));
+    assert!(stdout.contains("  EntryName              \"b.txt\"\n"));
+    assert!(stdout.contains("  Extension              \".txt\"\n"));
+    assert!(stdout.contains("  NumDirectoryParts      1\n"));
+}
+
+#[test]
+fn TEST_expand_JSON() {
+    let output = run_(&["expand", "--format", "json", "a/b.txt"]);
+
+    assert_eq!(Some(0), output.status.code());
+
+    let values = json_(&output);
+
+    assert_eq!("Relative", values[0]["classification"]);
+    assert_eq!("b.txt", values[0]["expanded"]["EntryName"]);
+    assert_eq!("b", values[0]["expanded"]["Stem"]);
+    assert_eq!(1, values[0]["expanded"]["NumDirectoryParts"]);
+}
+
+#[test]
+fn TEST_USAGE_ERRORS() {
+    for args in [
+        &["bogus"][..],
+        &[][..],
+        &["join", "/a"][..],
+        &["convert", "a"][..],
+        &["classify", "--syntax", "vms", "a"][..],
+    ] {
+        let output = run_(args);
+
+        assert_eq!(Some(2), output.status.code(), "{args:?}");
+        assert_eq!("", stdout_(&output), "{args:?}");
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -0,0 +1,189 @@
+/* /////////////////////////////////////////////////////////////////////////
+ * File:    tests/path_arena_memory.rs
+ *
+ * Purpose: Measurement of the memory used by PathArena, compared with that
+ *          used by holding each path as a String along with its
+ *          classification.
+ *
+ * Created: 18th October 2026
+ * Updated: 18th October 2026
+ *
+ * Home:    http://stlsoft.org/
+ *
+ * Copyright (c) 2026, Matthew Wilson and Synesis Information Systems
+ * All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions are
+ * met:
+ *
+ * - Redistributions of source code must retain the above copyright notice,
+ *   this list of conditions and the following disclaimer.
+ * - Redistributions in binary form must reproduce the above copyright
+ *   notice, this list of conditions and the following disclaimer in the
+ *   documentation and/or other materials provided with the distribution.
+ * - Neither the name of the copyright holder nor the names of its
+ *   contributors may be used to endorse or promote products derived from
+ *   this software without specific prior written permission.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS "AS
+ * IS" AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO,
+ * THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR
+ * PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT OWNER OR
+ * CONTRIBUTORS BE LIABLE FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL,
+ * EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT NOT LIMITED TO,
+ * PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR
+ * PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF
+ * LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING
+ * NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF THIS
+ * SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ *
+ * ////////////////////////////////////////////////////////////////////// */
+
+
+#![allow(non_snake_case)]
+
+use libpath::libpath::util::{
+    common::ClassificationResult,
+    unix::{
+        self,
+        PathArena,
+    },
+};
+
+use std::{
+    alloc::{
+        GlobalAlloc,
+        Layout,
+        System,
+    },
+    sync::atomic::{
+        AtomicUsize,
+        Ordering,
+    },
+};
+
+
+/// An allocator that counts the bytes currently allocated.
+struct CountingAllocator;
+
+static ALLOCATED : AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(
+        &self,
+        layout : Layout,
+    ) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst);
+
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(
+        &self,
+        ptr : *mut u8,
+        layout : Layout,
+    ) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr : *mut u8,
+        layout : Layout,
+        new_size : usize,
+    ) -> *mut u8 {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+        ALLOCATED.fetch_add(new_size, Ordering::SeqCst);
+
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL : CountingAllocator = CountingAllocator;
+
+
+/// Obtains the number of bytes allocated by `f` that remain allocated
+/// while its result is held.
+fn allocated_by_<T>(f : impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATED.load(Ordering::SeqCst);
+
+    let t = f();
+
+    let after = ALLOCATED.load(Ordering::SeqCst);
+
+    (t, after.saturating_sub(before))
+}
+
+/// Obtains the paths of a source tree of 10,000 files in 10 projects of 50
+/// modules each.
+fn paths_() -> Vec<String> {
+    let mut paths = Vec::new();
+
+    for project in 0..10 {
+        for module in 0..50 {
+            for file in 0..20 {
+                paths.push(format!(
+                    "/home/user/projects/project-{project}/src/modules/module-{module}/file-{file}.rs"
+                ));
+            }
+        }
+    }
+
+    paths
+}
+
+
+// The measurements are made within a single test, so that no other test
+// allocates concurrently.
+#[test]
+fn TEST_PathArena_USES_LESS_MEMORY_THAN_CLASSIFIED_STRINGS() {
+    let paths = paths_();
+
+    let text_len : usize = paths.iter().map(|path| path.len()).sum();
+
+    let (vec, vec_bytes) = allocated_by_(|| {
+        paths
+            .iter()
+            .map(|path| (path.clone(), unix::path_classify(path, 0).1))
+            .collect::<Vec<(String, ClassificationResult)>>()
+    });
+
+    let (arena, arena_bytes) = allocated_by_(|| {
+        let mut arena = PathArena::new();
+
+        arena.extend(paths.iter().map(String::as_str));
+
+        arena
+    });
+
+    assert_eq!(paths.len(), vec.len());
+    assert_eq!(paths.len(), arena.len());
+
+    // "/", "home/", "user/", "projects/", and, for each project,
+    // "project-N/", "src/", "modules/", and "module-N/" for each module
+
+    assert_eq!(4 + 10 * (3 + 50), arena.num_directories());
+
+    // the strings and classifications take more than the text of the
+    // paths, whereas the arena, which holds each directory only once, takes
+    // less than the text, and a fraction of the strings and
+    // classifications
+
+    assert!(vec_bytes > text_len + paths.len() * size_of::<ClassificationResult>());
+    assert!(arena_bytes < text_len, "arena_bytes={arena_bytes}, text_len={text_len}");
+    assert!(
+        arena_bytes * 5 < vec_bytes,
+        "arena_bytes={arena_bytes}, vec_bytes={vec_bytes}"
+    );
+
+    for (path, handle) in paths.iter().zip(arena.handles()) {
+        assert_eq!(*path, arena.path(handle));
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */